@@ -1,5 +1,7 @@
 use std::io::{self, Write};
 
+use crate::text;
+
 const CYCLE_COLORS: &[u8] = &[
     2, // green
     6, // cyan
@@ -47,7 +49,7 @@ pub fn run_foreground(logo: &str, total_height: u16, original_color: u8) {
     };
 
     let logo_lines: Vec<&str> = logo.lines().collect();
-    let logo_width = logo_lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let logo_width = logo_lines.iter().map(|l| text::width(l)).max().unwrap_or(0);
     let mut stdout = io::stdout();
     let mut frame: usize = 0;
 
@@ -76,8 +78,8 @@ pub fn run_foreground(logo: &str, total_height: u16, original_color: u8) {
             // Pad line to logo_width so we overwrite old content but don't touch the info area
             let _ = write!(
                 stdout,
-                "\x1b[{};1H\x1b[3{}m{:<width$}\x1b[0m",
-                row, color, line, width = logo_width
+                "\x1b[{};1H\x1b[3{}m{}\x1b[0m",
+                row, color, text::pad_right(line, logo_width)
             );
         }
         let _ = stdout.flush();
@@ -91,8 +93,8 @@ pub fn run_foreground(logo: &str, total_height: u16, original_color: u8) {
         let row = logo_start_row + i as u16;
         let _ = write!(
             stdout,
-            "\x1b[{};1H\x1b[3{}m{:<width$}\x1b[0m",
-            row, original_color, line, width = logo_width
+            "\x1b[{};1H\x1b[3{}m{}\x1b[0m",
+            row, original_color, text::pad_right(line, logo_width)
         );
     }
 
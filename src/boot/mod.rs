@@ -11,6 +11,7 @@ mod timeline;
 use std::io::{self, Write};
 use std::os::unix::io::FromRawFd;
 
+use crate::config::Config;
 use crate::info::SystemInfo;
 use canvas::Canvas;
 use timeline::{Phase, Timeline};
@@ -28,6 +29,7 @@ const FOOTER_COLOR: (u8, u8, u8) = (140, 140, 140);
 
 pub fn run(
     info: &SystemInfo,
+    cfg: &Config,
     centered: bool,
     right_aligned: bool,
     mut bg: Option<Vec<background::BgCell>>,
@@ -75,7 +77,7 @@ pub fn run(
     let mut bh = base_bh + image_extra_h;
 
     // Pre-build content
-    let status_text = status::build_line(info);
+    let status_text = status::build_line(info, cfg);
 
     // Check terminal size
     let (term_width, term_height) = terminal_size();
@@ -413,7 +415,7 @@ pub fn run(
 }
 
 /// Draw the collapsing border frame.
-fn draw_collapsed_frame(canvas: &mut Canvas, status_text: &str, progress: f32, bh: u16) {
+fn draw_collapsed_frame(canvas: &mut Canvas, status_text: &status::StatusLine, progress: f32, bh: u16) {
     let w = canvas.width;
     let max_travel = bh - 1 - 2;
     let travel = (max_travel as f32 * progress) as u16;
@@ -538,6 +540,7 @@ pub fn terminal_size() -> (u16, u16) {
 /// Renders the full UI: border, status bar, background, footer.
 pub fn run_inline(
     info: &SystemInfo,
+    cfg: &Config,
     bg: Option<Vec<background::BgCell>>,
     w: u16,
     h: u16,
@@ -555,7 +558,7 @@ pub fn run_inline(
     border::draw_border(&mut canvas, BORDER_COLOR, 1.0);
 
     // Draw status bar
-    let status_text = status::build_line(info);
+    let status_text = status::build_line(info, cfg);
     status::draw(&mut canvas, &status_text, 1, STATUS_COLOR, 1.0);
 
     // Draw footer
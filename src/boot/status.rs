@@ -1,10 +1,24 @@
 use super::canvas::Canvas;
+use crate::config::{Config, FieldDisplay};
+use crate::gauge::Gauge;
 use crate::info::SystemInfo;
 
+/// Cells in a status line gauge.
+const GAUGE_WIDTH: u16 = 5;
+
+/// The status text and the colored gauges within it.
+pub struct StatusLine {
+    pub text: String,
+    /// Character ranges drawn in their own color
+    pub colors: Vec<(std::ops::Range<usize>, (u8, u8, u8))>,
+}
+
 /// Build a compact one-line status string from system info.
 /// Format: "macOS 15.5 · M3 Pro · 28/36 GiB · 250/500 GiB · 33d up"
-pub fn build_line(info: &SystemInfo) -> String {
+/// Memory and disk get a small gauge when `[format]` draws them as bars.
+pub fn build_line(info: &SystemInfo, cfg: &Config) -> StatusLine {
     let mut parts = Vec::new();
+    let mut gauges = Vec::new();
 
     // OS — strip architecture suffix
     let os = info
@@ -24,18 +38,48 @@ pub fn build_line(info: &SystemInfo) -> String {
 
     // Memory — convert MiB to GiB compact
     if let Some(mem) = compact_memory(&info.memory) {
+        gauges.push((parts.len(), gauge(info, cfg, "Memory", &info.memory)));
         parts.push(mem);
     }
 
     // Disk/SSD — compact
     if let Some(disk) = compact_disk(&info.disk) {
+        gauges.push((parts.len(), gauge(info, cfg, "Disk (/)", &info.disk)));
         parts.push(disk);
     }
 
     // Uptime — compact
     parts.push(compact_uptime(&info.uptime));
 
-    parts.join(" · ")
+    // Join, appending each gauge to its part and noting where its filled cells land
+    let mut line = StatusLine {
+        text: String::new(),
+        colors: Vec::new(),
+    };
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            line.text.push_str(" · ");
+        }
+        line.text.push_str(part);
+        if let Some((_, Some(g))) = gauges.iter().find(|(at, _)| *at == i) {
+            let (filled, empty) = g.cells();
+            let start = line.text.chars().count() + 1;
+            line.colors.push((start..start + g.filled, g.level.rgb()));
+            line.text.push(' ');
+            line.text.push_str(&filled);
+            line.text.push_str(&empty);
+        }
+    }
+    line
+}
+
+/// Compact gauge for a field drawn as a bar in `[format]`.
+fn gauge(info: &SystemInfo, cfg: &Config, field: &str, value: &str) -> Option<Gauge> {
+    let display = FieldDisplay {
+        width: Some(GAUGE_WIDTH),
+        ..cfg.bar_for(field)?.clone()
+    };
+    Gauge::new(info, field, value, &display)
 }
 
 fn compact_memory(mem: &str) -> Option<String> {
@@ -75,9 +119,11 @@ fn compact_uptime(uptime: &str) -> String {
 
 /// Draw the status bar at a given row.
 /// `progress` controls the typing animation (0.0–1.0).
-pub fn draw(canvas: &mut Canvas, text: &str, row: u16, color: (u8, u8, u8), progress: f32) {
-    let total_chars = text.chars().count();
+pub fn draw(canvas: &mut Canvas, line: &StatusLine, row: u16, color: (u8, u8, u8), progress: f32) {
+    let total_chars = line.text.chars().count();
     let max_chars = ((total_chars as f32) * progress) as usize;
-    let visible: String = text.chars().take(max_chars).collect();
-    canvas.put_str(2, row, &visible, color, None);
+    for (i, ch) in line.text.chars().take(max_chars).enumerate() {
+        let fg = line.colors.iter().find(|(r, _)| r.contains(&i)).map_or(color, |(_, c)| *c);
+        canvas.set(2 + i as u16, row, ch, fg, None);
+    }
 }
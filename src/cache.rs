@@ -1,18 +1,75 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bump when the on-disk layout changes so older files are discarded.
+const FORMAT_VERSION: u32 = 2;
 
 pub fn clear() {
-    let path = cache_path();
-    let _ = std::fs::remove_file(&path);
+    if let Some(path) = cache_path() {
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// How long a gathered value is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Cached until the next reboot (or blaeckfetch upgrade).
+    Boot,
+    /// Never cached — computed on every run.
+    Fresh,
+}
+
+impl Policy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Policy::Boot => "boot",
+            Policy::Fresh => "fresh",
+        }
+    }
+}
+
+/// On-disk representation of the cache.
+/// Entries are only trusted when the format, the blaeckfetch version and the
+/// boot time all match — a binary upgrade may change how values are computed.
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    format: u32,
+    version: String,
+    boot_time: u64,
+    entries: HashMap<String, Entry>,
+}
+
+/// A cached value and when it was computed (seconds since the Unix epoch).
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Entry {
+    pub value: String,
+    pub updated: u64,
+}
+
+impl Entry {
+    fn new(value: String) -> Self {
+        Self {
+            value,
+            updated: now(),
+        }
+    }
+
+    /// Seconds since this entry was computed.
+    pub fn age(&self) -> u64 {
+        now().saturating_sub(self.updated)
+    }
 }
 
 /// Simple key-value cache persisted to disk.
-/// Invalidated when system boot time changes.
+/// Invalidated when system boot time or the blaeckfetch version changes.
 pub struct Cache {
-    path: PathBuf,
+    path: Option<PathBuf>,
     boot_time: u64,
-    entries: HashMap<String, String>,
+    entries: HashMap<String, Entry>,
     dirty: bool,
 }
 
@@ -20,7 +77,10 @@ impl Cache {
     pub fn load() -> Self {
         let path = cache_path();
         let boot_time = get_boot_time();
-        let entries = read_cache(&path, boot_time);
+        let entries = path
+            .as_deref()
+            .map(|p| read_cache(p, boot_time))
+            .unwrap_or_default();
         Self {
             path,
             boot_time,
@@ -29,43 +89,80 @@ impl Cache {
         }
     }
 
+    /// Location of the cache file, if a usable cache directory exists.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
     /// Get a cached value, or compute and cache it.
     pub fn get_or_insert(&mut self, key: &str, f: impl FnOnce() -> String) -> String {
-        if let Some(val) = self.entries.get(key) {
-            return val.clone();
+        if let Some(entry) = self.entries.get(key) {
+            return entry.value.clone();
         }
+        self.refresh(key, f)
+    }
+
+    /// Recompute a value and replace whatever was cached for it.
+    pub fn refresh(&mut self, key: &str, f: impl FnOnce() -> String) -> String {
         let val = f();
-        self.entries.insert(key.to_string(), val.clone());
+        self.entries.insert(key.to_string(), Entry::new(val.clone()));
         self.dirty = true;
         val
     }
 
     /// Write cache to disk if anything changed.
+    /// Writes to a temporary file first and renames it into place, so
+    /// concurrent runs never observe a half-written cache.
     pub fn save(&self) {
-        if !self.dirty {
-            return;
-        }
-        if let Some(parent) = self.path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        let mut content = format!("boot_time={}\n", self.boot_time);
-        for (k, v) in &self.entries {
-            // Simple line-based format, escape newlines
-            content.push_str(&format!("{}={}\n", k, v.replace('\n', "\\n")));
-        }
-        let _ = fs::write(&self.path, content);
+        let path = match &self.path {
+            Some(p) if self.dirty => p,
+            _ => return,
+        };
+        let file = CacheFile {
+            format: FORMAT_VERSION,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            boot_time: self.boot_time,
+            entries: self.entries.clone(),
+        };
+        let content = match serde_json::to_string(&file) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let _ = write_atomic(path, content.as_bytes());
     }
 }
 
-fn cache_path() -> PathBuf {
-    if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home)
-            .join(".cache")
-            .join("blaeckfetch")
-            .join("cache")
-    } else {
-        PathBuf::from("/tmp/blaeckfetch-cache")
+fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "cache".into());
+    let tmp = parent.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let result = fs::File::create(&tmp)
+        .and_then(|mut f| f.write_all(content).and_then(|_| f.sync_all()))
+        .and_then(|_| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
     }
+    result
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Cache file location, or None if no safe directory is available.
+fn cache_path() -> Option<PathBuf> {
+    crate::xdg::cache_home().map(|d| d.join("cache"))
 }
 
 fn get_boot_time() -> u64 {
@@ -129,28 +226,23 @@ fn get_boot_time() -> u64 {
     }
 }
 
-fn read_cache(path: &PathBuf, current_boot_time: u64) -> HashMap<String, String> {
+fn read_cache(path: &Path, current_boot_time: u64) -> HashMap<String, Entry> {
     let content = match fs::read_to_string(path) {
         Ok(c) => c,
         Err(_) => return HashMap::new(),
     };
 
-    let mut map = HashMap::new();
-    let mut cached_boot_time: Option<u64> = None;
-
-    for line in content.lines() {
-        if let Some((key, val)) = line.split_once('=') {
-            if key == "boot_time" {
-                cached_boot_time = val.parse().ok();
-            } else {
-                map.insert(key.to_string(), val.replace("\\n", "\n"));
-            }
-        }
-    }
+    // Anything unparseable (including the old key=value format) is treated as empty
+    let file: CacheFile = match serde_json::from_str(&content) {
+        Ok(f) => f,
+        Err(_) => return HashMap::new(),
+    };
 
-    // Invalidate if boot time changed
-    match cached_boot_time {
-        Some(bt) if bt == current_boot_time => map,
-        _ => HashMap::new(),
+    if file.format != FORMAT_VERSION
+        || file.version != env!("CARGO_PKG_VERSION")
+        || file.boot_time != current_boot_time
+    {
+        return HashMap::new();
     }
+    file.entries
 }
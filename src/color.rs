@@ -8,6 +8,8 @@ pub struct Theme {
     pub label: Color,
     pub separator: Color,
     pub logo: Color,
+    /// Value color; None leaves values in the terminal's default color
+    pub value: Option<Color>,
 }
 
 impl Theme {
@@ -30,6 +32,7 @@ impl Theme {
             label: Color::Green,
             separator: Color::DarkGray,
             logo: Color::Green,
+            value: None,
         }
     }
 
@@ -40,6 +43,7 @@ impl Theme {
             label: Color::Cyan,
             separator: Color::DarkGray,
             logo: Color::Cyan,
+            value: None,
         }
     }
 
@@ -50,6 +54,7 @@ impl Theme {
             label: Color::Red,
             separator: Color::DarkGray,
             logo: Color::Red,
+            value: None,
         }
     }
 
@@ -60,6 +65,7 @@ impl Theme {
             label: Color::Magenta,
             separator: Color::DarkGray,
             logo: Color::Magenta,
+            value: None,
         }
     }
 
@@ -70,6 +76,7 @@ impl Theme {
             label: Color::Yellow,
             separator: Color::DarkGray,
             logo: Color::Yellow,
+            value: None,
         }
     }
 
@@ -80,6 +87,7 @@ impl Theme {
             label: Color::Blue,
             separator: Color::DarkGray,
             logo: Color::Blue,
+            value: None,
         }
     }
 
@@ -90,6 +98,7 @@ impl Theme {
             label: Color::White,
             separator: Color::DarkGray,
             logo: Color::White,
+            value: None,
         }
     }
 
@@ -120,3 +129,16 @@ pub fn color_to_ansi(c: &Color) -> u8 {
         _ => 2, // default to green
     }
 }
+
+/// Whether to write colors and other escapes, from `color_mode`:
+/// `always`, `never`, or `auto` (default) for a terminal with `NO_COLOR` unset.
+pub fn enabled(mode: Option<&str>) -> bool {
+    match mode {
+        Some("always") => true,
+        Some("never") => false,
+        _ => {
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+            !no_color && unsafe { libc::isatty(1) == 1 }
+        }
+    }
+}
@@ -0,0 +1,625 @@
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+use super::{Config, COLOR_NAMES};
+use crate::field;
+
+/// A config problem, located in the file (or override) it came from.
+pub struct Diagnostic {
+    pub origin: String,
+    /// 1-based line and column; 0 when the problem has no position (e.g. unreadable file).
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(file: &Path, content: &str, span: Option<Range<usize>>, message: String) -> Self {
+        let (line, col) = span
+            .map(|s| line_col(content, s.start))
+            .unwrap_or((0, 0));
+        Self {
+            origin: file.display().to_string(),
+            line,
+            col,
+            message,
+        }
+    }
+
+    /// A problem with no position, e.g. an unreadable file or a `--set` override.
+    pub fn plain(origin: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            origin: origin.into(),
+            line: 0,
+            col: 0,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "{}:{}:{}: {}", self.origin, self.line, self.col, self.message)
+        } else {
+            write!(f, "{}: {}", self.origin, self.message)
+        }
+    }
+}
+
+fn line_col(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let col = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    (line, col)
+}
+
+/// One step in the path to a config value, used to drop invalid entries.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Seg {
+    Key(String),
+    Index(usize),
+}
+
+/// A problem found by `check`, with the path of the entry to drop.
+pub struct Problem {
+    pub path: Vec<Seg>,
+    pub span: Option<Range<usize>>,
+    pub message: String,
+}
+
+/// Expected shape of a config value.
+pub enum Kind {
+    Str,
+    Bool,
+    UInt(u64),
+    /// One of a fixed set of strings (exact match)
+    OneOf(&'static [&'static str]),
+    /// One of a fixed set of names (case-insensitive)
+    Name(&'static [&'static str]),
+    /// Named color or [r, g, b]
+    Color,
+    /// A field name such as "OS" or "Disk (/)"
+    Field,
+    List(&'static Kind),
+    StrOrList,
+    /// Table with a fixed set of keys, given as one or more key lists
+    Table(&'static [&'static [(&'static str, Kind)]]),
+    /// Table with arbitrary keys
+    Map(&'static Kind),
+    /// Table keyed by field name
+    FieldMap(&'static Kind),
+    /// A value of the inner kind, or a list of them (`[[x]]` tables included)
+    OneOrList(&'static Kind),
+    /// A template checked against the placeholders of a field; `""` means the
+    /// field it is keyed by, as in `[format]`
+    Template(&'static str),
+    /// A value of the first kind, or a table of the second (`[format]` entries)
+    OrTable(&'static Kind, &'static Kind),
+}
+
+const SPLASH: &[(&str, Kind)] = &[
+    ("image", Kind::Str),
+    ("stretch", Kind::OneOf(&["fill", "fit", "crop"])),
+    ("transparency", Kind::UInt(255)),
+    ("width", Kind::UInt(u16::MAX as u64)),
+    ("height", Kind::UInt(u16::MAX as u64)),
+    ("timeout", Kind::UInt(u32::MAX as u64)),
+    ("star_brightness", Kind::UInt(255)),
+    ("render_mode", Kind::OneOf(&["auto", "image", "ascii", "inline"])),
+    ("align", Kind::OneOf(&["left", "center", "right"])),
+    ("min_width", Kind::UInt(u16::MAX as u64)),
+    ("min_height", Kind::UInt(u16::MAX as u64)),
+    ("max_width", Kind::UInt(u16::MAX as u64)),
+    ("max_height", Kind::UInt(u16::MAX as u64)),
+    ("entrance", Kind::OneOf(&["slow", "fast", "instant"])),
+    ("exit", Kind::OneOf(&["slow", "fast", "instant"])),
+];
+
+const EXPORT: &[(&str, Kind)] = &[
+    ("font", Kind::Str),
+    ("font_size", Kind::UInt(200)),
+    ("background", Kind::Color),
+    ("foreground", Kind::Color),
+];
+
+/// A `[when.<field>]` rule: conditions, and what to do when they all hold.
+const WHEN: &[(&str, Kind)] = &[
+    ("above", Kind::UInt(u64::MAX)),
+    ("below", Kind::UInt(u64::MAX)),
+    ("equals", Kind::Str),
+    ("unknown", Kind::Bool),
+    ("color", Kind::Color),
+    ("hide", Kind::Bool),
+];
+
+/// A `[format.<field>]` table.
+const FORMAT: &[(&str, Kind)] = &[
+    ("style", Kind::OneOf(&["text", "bar"])),
+    ("width", Kind::UInt(200)),
+    ("text", Kind::Template("")),
+    ("warn", Kind::UInt(100)),
+    ("critical", Kind::UInt(100)),
+];
+
+const LOGO_PADDING: &[(&str, Kind)] = &[
+    ("top", Kind::UInt(u16::MAX as u64)),
+    ("left", Kind::UInt(u16::MAX as u64)),
+    ("gap", Kind::UInt(u16::MAX as u64)),
+];
+
+const COLORS: &[(&str, Kind)] = &[
+    ("title", Kind::Color),
+    ("label", Kind::Color),
+    ("separator", Kind::Color),
+    ("logo", Kind::Color),
+    ("value", Kind::Color),
+    ("border", Kind::Color),
+];
+
+const TEXT_STYLE: &[(&str, Kind)] = &[
+    ("color", Kind::Color),
+    ("bold", Kind::Bool),
+    ("italic", Kind::Bool),
+    ("underline", Kind::Bool),
+    ("dim", Kind::Bool),
+];
+
+const FIELD_STYLE: &[(&str, Kind)] = &[
+    ("label", Kind::Table(&[TEXT_STYLE])),
+    ("value", Kind::Table(&[TEXT_STYLE])),
+];
+
+const STYLE: &[(&str, Kind)] = &[
+    ("header", Kind::Table(&[TEXT_STYLE])),
+    ("label", Kind::Table(&[TEXT_STYLE])),
+    ("value", Kind::Table(&[TEXT_STYLE])),
+    ("field", Kind::FieldMap(&Kind::Table(&[FIELD_STYLE]))),
+];
+
+/// Keys that map onto `Config` and may appear at the top level or in override tables.
+const GENERAL: &[(&str, Kind)] = &[
+    ("mode", Kind::Name(&["default", "neofetch", "splash", "boot"])),
+    ("color", Kind::Name(&["green", "cyan", "red", "magenta", "pink", "yellow", "blue", "white", "mono"])),
+    ("logo", Kind::Name(&[
+        "auto", "apple", "macos", "mac", "linux", "tux", "ubuntu", "arch", "debian", "fedora", "moon", "none", "off",
+    ])),
+    ("logo_file", Kind::Str),
+    ("separator", Kind::Str),
+    ("title_format", Kind::Template("Title")),
+    ("logo_position", Kind::OneOf(&["left", "right", "top", "bottom"])),
+    ("logo_padding", Kind::Table(&[LOGO_PADDING])),
+    ("vertical_align", Kind::OneOf(&["top", "center", "bottom"])),
+    ("border", Kind::OneOf(&["rounded", "single", "double", "bold", "ascii", "none"])),
+    ("border_around", Kind::OneOf(&["info", "all"])),
+    ("border_title", Kind::Template("Title")),
+    ("responsive", Kind::Bool),
+    ("color_mode", Kind::OneOf(&["auto", "always", "never"])),
+    ("palette_glyph", Kind::Str),
+    ("palette_width", Kind::UInt(16)),
+    ("palette_spacing", Kind::UInt(16)),
+    ("palette_rows", Kind::UInt(2)),
+    ("palette_colors", Kind::OneOf(&["ansi", "256", "truecolor"])),
+    ("palette_position", Kind::OneOf(&["info", "logo"])),
+    ("divider", Kind::OneOf(&["dashed", "solid", "double", "dotted", "bold", "ascii", "none"])),
+    ("colors", Kind::Table(&[COLORS])),
+    ("style", Kind::Table(&[STYLE])),
+    ("format", Kind::FieldMap(&Kind::OrTable(&Kind::Template(""), &Kind::Table(&[FORMAT])))),
+    ("when", Kind::FieldMap(&Kind::OneOrList(&Kind::Table(&[WHEN])))),
+    ("splash", Kind::Table(&[SPLASH])),
+    ("boot", Kind::Table(&[SPLASH])),
+    ("export", Kind::Table(&[EXPORT])),
+];
+
+/// Layout keys, which can also be set per mode in `[default]` and `[neofetch]`.
+const LAYOUT: &[(&str, Kind)] = &[
+    ("palette", Kind::Bool),
+    ("label_align", Kind::OneOf(&["left", "right"])),
+    ("label_position", Kind::OneOf(&["left", "right"])),
+    ("value_align", Kind::OneOf(&["left", "right"])),
+    ("field_separator", Kind::Str),
+    ("fields", Kind::List(&Kind::Field)),
+    ("labels", Kind::FieldMap(&Kind::Str)),
+    ("hide_unknown", Kind::Bool),
+    ("show_title", Kind::Bool),
+    ("columns", Kind::UInt(8)),
+];
+
+const MODES: &[(&str, Kind)] = &[
+    ("default", Kind::Table(&[LAYOUT])),
+    ("neofetch", Kind::Table(&[LAYOUT])),
+];
+
+const OVERRIDE: Kind = Kind::Table(&[GENERAL, LAYOUT, MODES]);
+
+/// Keys that only make sense at the top level of a file.
+const STRUCTURE: &[(&str, Kind)] = &[
+    ("include", Kind::StrOrList),
+    ("os", Kind::Table(&[&[("macos", OVERRIDE), ("linux", OVERRIDE)]])),
+    ("host", Kind::Map(&OVERRIDE)),
+    ("profile", Kind::Map(&OVERRIDE)),
+];
+
+const FILE: Kind = Kind::Table(&[GENERAL, LAYOUT, MODES, STRUCTURE]);
+
+/// Validate a parsed config file against the schema.
+pub fn check<S>(doc: &ImDocument<S>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    check_table(doc.as_table(), &FILE, &mut Vec::new(), &mut problems);
+    problems
+}
+
+/// Validate a document holding only settings, as built for env and `--set` overrides.
+pub fn check_settings<S>(doc: &ImDocument<S>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    check_table(doc.as_table(), &OVERRIDE, &mut Vec::new(), &mut problems);
+    problems
+}
+
+/// Schema for a setting path such as `["splash", "timeout"]`, if known.
+pub fn kind_at(path: &[&str]) -> Option<&'static Kind> {
+    let mut kind: &'static Kind = &OVERRIDE;
+    for key in path {
+        if let Kind::OneOrList(inner) | Kind::OrTable(_, inner) = kind {
+            kind = inner;
+        }
+        kind = match kind {
+            Kind::Table(groups) => groups.iter().flat_map(|g| g.iter()).find(|(k, _)| k == key).map(|(_, k)| k)?,
+            Kind::Map(inner) | Kind::FieldMap(inner) => inner,
+            _ => return None,
+        };
+    }
+    Some(kind)
+}
+
+fn check_item(item: &Item, kind: &Kind, path: &mut Vec<Seg>, out: &mut Vec<Problem>) {
+    let span = item.span();
+    let fail = |message: String, out: &mut Vec<Problem>| {
+        out.push(Problem {
+            path: path.clone(),
+            span: span.clone(),
+            message,
+        })
+    };
+    let name = describe(path);
+
+    match kind {
+        Kind::Table(_) | Kind::Map(_) | Kind::FieldMap(_) => match item.as_table_like() {
+            Some(t) => check_table(t, kind, path, out),
+            None => fail(format!("`{}` must be a table, found {}", name, type_name(item)), out),
+        },
+        Kind::OneOrList(inner) => {
+            if let Some(tables) = item.as_array_of_tables() {
+                for (i, t) in tables.iter().enumerate() {
+                    path.push(Seg::Index(i));
+                    check_item(&Item::Table(t.clone()), inner, path, out);
+                    path.pop();
+                }
+            } else if let Some(arr) = item.as_array() {
+                for (i, v) in arr.iter().enumerate() {
+                    path.push(Seg::Index(i));
+                    check_item(&Item::Value(v.clone()), inner, path, out);
+                    path.pop();
+                }
+            } else {
+                check_item(item, inner, path, out);
+            }
+        }
+        Kind::OrTable(plain, table) => {
+            let kind = if item.is_table_like() { table } else { plain };
+            check_item(item, kind, path, out);
+        }
+        Kind::List(inner) => match item.as_array() {
+            Some(arr) => {
+                for (i, v) in arr.iter().enumerate() {
+                    path.push(Seg::Index(i));
+                    check_item(&Item::Value(v.clone()), inner, path, out);
+                    path.pop();
+                }
+            }
+            None => fail(format!("`{}` must be a list, found {}", name, type_name(item)), out),
+        },
+        Kind::StrOrList => {
+            let ok = item.is_str()
+                || item
+                    .as_array()
+                    .is_some_and(|a| a.iter().all(|v| v.is_str()));
+            if !ok {
+                fail(format!("`{}` must be a string or list of strings", name), out);
+            }
+        }
+        Kind::Str => {
+            if !item.is_str() {
+                fail(format!("`{}` must be a string, found {}", name, type_name(item)), out);
+            }
+        }
+        Kind::Bool => {
+            if !item.is_bool() {
+                fail(format!("`{}` must be true or false, found {}", name, type_name(item)), out);
+            }
+        }
+        Kind::UInt(max) => match item.as_integer() {
+            Some(n) if n >= 0 && n as u64 <= *max => {}
+            Some(n) => fail(format!("`{}` must be between 0 and {}, found {}", name, max, n), out),
+            None => fail(format!("`{}` must be a number, found {}", name, type_name(item)), out),
+        },
+        Kind::OneOf(allowed) | Kind::Name(allowed) => {
+            let case_insensitive = matches!(kind, Kind::Name(_));
+            match item.as_str() {
+                Some(s) => {
+                    let valid = if case_insensitive {
+                        allowed.iter().any(|a| a.eq_ignore_ascii_case(s))
+                    } else {
+                        allowed.contains(&s)
+                    };
+                    if !valid {
+                        fail(unknown_value(&name, s, allowed.iter().copied()), out);
+                    }
+                }
+                None => fail(format!("`{}` must be a string, found {}", name, type_name(item)), out),
+            }
+        }
+        Kind::Color => match item.as_value() {
+            Some(Value::String(s)) => {
+                let s = s.value();
+                if !COLOR_NAMES.iter().any(|c| c.eq_ignore_ascii_case(s)) {
+                    fail(unknown_value(&name, s, COLOR_NAMES.iter().copied()), out);
+                }
+            }
+            Some(Value::Array(arr)) => {
+                let ok = arr.len() == 3
+                    && arr
+                        .iter()
+                        .all(|v| v.as_integer().is_some_and(|n| (0..=255).contains(&n)));
+                if !ok {
+                    fail(format!("`{}` must be [r, g, b] with values 0-255", name), out);
+                }
+            }
+            _ => fail(format!("`{}` must be a color name or [r, g, b]", name), out),
+        },
+        Kind::Field => match item.as_str() {
+            Some(s) if super::is_layout_entry(s) => {}
+            Some(s) => {
+                let fields = Config::default_fields();
+                if !fields.iter().any(|f| f == s) {
+                    fail(unknown_value("field", s, fields.iter().map(String::as_str)), out);
+                }
+            }
+            None => fail(format!("`{}` must be a field name string", name), out),
+        },
+        Kind::Template(field) => match item.as_str() {
+            Some(s) => {
+                // An empty field name means the template is keyed by field, as in `[format]`
+                let fields = Config::default_fields();
+                let keyed_by = path.iter().rev().find_map(|seg| match seg {
+                    Seg::Key(k) if fields.contains(k) => Some(k.as_str()),
+                    _ => None,
+                });
+                let field = match (field, keyed_by) {
+                    (&"", Some(k)) => k,
+                    (f, _) => f,
+                };
+                let known = field::placeholders(field).iter().map(|(p, _)| *p);
+                let known: Vec<&str> = std::iter::once("value").chain(known).collect();
+                match field::placeholders_in(s) {
+                    Ok(used) => {
+                        if let Some(p) = used.iter().find(|p| !known.contains(&p.as_str())) {
+                            let what = format!("placeholder in `{}`", name);
+                            fail(unknown_value(&what, p, known.iter().copied()), out);
+                        }
+                    }
+                    Err(e) => fail(format!("`{}`: {}", name, e), out),
+                }
+            }
+            None => fail(format!("`{}` must be a string, found {}", name, type_name(item)), out),
+        },
+    }
+}
+
+fn check_table(table: &dyn TableLike, kind: &Kind, path: &mut Vec<Seg>, out: &mut Vec<Problem>) {
+    for (key, item) in table.iter() {
+        let key_span = table.get_key_value(key).and_then(|(k, _)| k.span());
+        path.push(Seg::Key(key.to_string()));
+
+        let child: Option<&Kind> = match kind {
+            Kind::Table(groups) => {
+                let found = groups.iter().flat_map(|g| g.iter()).find(|(k, _)| *k == key);
+                if found.is_none() {
+                    let known = groups.iter().flat_map(|g| g.iter()).map(|(k, _)| *k);
+                    out.push(Problem {
+                        path: path.clone(),
+                        span: key_span,
+                        message: unknown_key(&describe(path), key, known),
+                    });
+                }
+                found.map(|(_, k)| k)
+            }
+            Kind::Map(inner) => Some(*inner),
+            Kind::FieldMap(inner) => {
+                let fields = Config::default_fields();
+                if !fields.iter().any(|f| f == key) {
+                    out.push(Problem {
+                        path: path.clone(),
+                        span: key_span,
+                        message: unknown_value("field", key, fields.iter().map(String::as_str)),
+                    });
+                    None
+                } else {
+                    Some(*inner)
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(child) = child {
+            check_item(item, child, path, out);
+        }
+        path.pop();
+    }
+}
+
+fn unknown_key<'a>(path: &str, key: &str, known: impl Iterator<Item = &'a str>) -> String {
+    match suggest(key, known) {
+        Some(s) => format!("unknown key `{}` (did you mean `{}`?)", path, s),
+        None => format!("unknown key `{}`", path),
+    }
+}
+
+fn unknown_value<'a>(what: &str, value: &str, allowed: impl Iterator<Item = &'a str> + Clone) -> String {
+    match suggest(value, allowed.clone()) {
+        Some(s) => format!("invalid {} \"{}\" (did you mean \"{}\"?)", what, value, s),
+        None => {
+            let list: Vec<&str> = allowed.collect();
+            format!("invalid {} \"{}\" (expected one of: {})", what, value, list.join(", "))
+        }
+    }
+}
+
+/// Closest candidate within a small edit distance, or one the input is a prefix of.
+pub fn suggest<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let input_lc = input.to_lowercase();
+    let max = (input.chars().count() / 3).max(1);
+    candidates
+        .filter_map(|c| {
+            let c_lc = c.to_lowercase();
+            let d = edit_distance(&input_lc, &c_lc);
+            if d <= max {
+                Some((d, c))
+            } else if input_lc.len() >= 2 && c_lc.starts_with(&input_lc) {
+                Some((max + 1, c))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions,
+/// so "cayn" is one edit from "cyan".
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Dotted path for messages, e.g. `colors.title` or `fields[2]`.
+fn describe(path: &[Seg]) -> String {
+    let mut s = String::new();
+    for seg in path {
+        match seg {
+            Seg::Key(k) => {
+                if !s.is_empty() {
+                    s.push('.');
+                }
+                s.push_str(k);
+            }
+            Seg::Index(i) => s.push_str(&format!("[{}]", i)),
+        }
+    }
+    s
+}
+
+fn type_name(item: &Item) -> &'static str {
+    match item {
+        Item::None => "nothing",
+        Item::Table(_) | Item::ArrayOfTables(_) => "a table",
+        Item::Value(v) => match v {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Float(_) => "a float",
+            Value::Boolean(_) => "a boolean",
+            Value::Datetime(_) => "a datetime",
+            Value::Array(_) => "a list",
+            Value::InlineTable(_) => "a table",
+        },
+    }
+}
+
+/// Drop every entry named by `paths` from a parsed table.
+/// Array entries are removed from the highest index down so earlier indices stay valid.
+pub fn remove_paths(table: &mut toml::Table, mut paths: Vec<Vec<Seg>>) {
+    paths.sort();
+    paths.dedup();
+    for path in paths.iter().rev() {
+        remove_path(table, path);
+    }
+}
+
+fn remove_path(table: &mut toml::Table, path: &[Seg]) {
+    let Some((Seg::Key(first), rest)) = path.split_first() else { return };
+    if rest.is_empty() || table.get_mut(first).is_some_and(|v| remove_in(v, rest)) {
+        table.remove(first);
+    }
+}
+
+/// Remove the entry at `path` inside `value`. Returns true when that leaves
+/// `value` an empty table, so the caller drops it too rather than keeping a
+/// header with nothing valid under it.
+fn remove_in(value: &mut toml::Value, path: &[Seg]) -> bool {
+    let Some((seg, rest)) = path.split_first() else { return false };
+    match (seg, value) {
+        (Seg::Key(k), toml::Value::Table(t)) => {
+            if rest.is_empty() || t.get_mut(k).is_some_and(|v| remove_in(v, rest)) {
+                t.remove(k);
+            }
+            t.is_empty()
+        }
+        (Seg::Index(idx), toml::Value::Array(a)) => {
+            if rest.is_empty() {
+                if *idx < a.len() {
+                    a.remove(*idx);
+                }
+            } else if let Some(v) = a.get_mut(*idx) {
+                remove_in(v, rest);
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_path(keys: &[&str]) -> Vec<Seg> {
+        keys.iter().map(|k| Seg::Key(k.to_string())).collect()
+    }
+
+    #[test]
+    fn removing_the_last_key_drops_its_tables() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+            colors = { title = "nope" }
+            style = { field = { Memory = { value = { color = "bad" } } }, label = { bold = true } }
+            "#,
+        )
+        .unwrap();
+        remove_paths(
+            &mut table,
+            vec![key_path(&["colors", "title"]), key_path(&["style", "field", "Memory", "value", "color"])],
+        );
+        assert!(!table.contains_key("colors"));
+        assert!(!table["style"].as_table().unwrap().contains_key("field"));
+        assert!(table["style"].get("label").is_some());
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use toml_edit::ImDocument;
+
+use super::check::{self, Diagnostic};
+
+/// Guards against include cycles and runaway nesting.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Read a config file and resolve its `include` list.
+/// Included files form the base; the including file's own keys win.
+/// Problems are pushed to `diags` and the offending entries dropped, so every
+/// valid part of a partially broken file is kept.
+pub fn load_file(path: &Path, diags: &mut Vec<Diagnostic>) -> Result<Table, String> {
+    load_with_includes(path, &mut Vec::new(), diags)
+}
+
+fn load_with_includes(path: &Path, stack: &mut Vec<PathBuf>, diags: &mut Vec<Diagnostic>) -> Result<Table, String> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        return Err(format!("include cycle through {}", path.display()));
+    }
+    if stack.len() >= MAX_INCLUDE_DEPTH {
+        return Err(format!("includes nested too deeply at {}", path.display()));
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+    let doc = match ImDocument::parse(content.as_str()) {
+        Ok(d) => d,
+        Err(e) => {
+            let message: Vec<&str> = e.message().lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+            diags.push(Diagnostic::new(path, &content, e.span(), message.join(": ")));
+            return Ok(Table::new());
+        }
+    };
+
+    let problems = check::check(&doc);
+    let mut table: Table = toml::from_str(&content).unwrap_or_default();
+    let mut invalid = Vec::new();
+    for p in problems {
+        diags.push(Diagnostic::new(path, &content, p.span, p.message));
+        invalid.push(p.path);
+    }
+    check::remove_paths(&mut table, invalid);
+
+    let includes: Vec<(String, Option<std::ops::Range<usize>>)> = match doc.get("include") {
+        Some(item) if item.is_str() => vec![(item.as_str().unwrap_or_default().to_string(), item.span())],
+        Some(item) => item
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(|s| (s.to_string(), v.span())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    table.remove("include");
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    stack.push(canonical);
+    let mut merged = Table::new();
+    for (inc, span) in includes {
+        let inc_path = resolve(base_dir, &inc);
+        match load_with_includes(&inc_path, stack, diags) {
+            Ok(t) => merge(&mut merged, t),
+            Err(e) => diags.push(Diagnostic::new(path, &content, span, format!("include \"{}\": {}", inc, e))),
+        }
+    }
+    stack.pop();
+
+    normalize_aliases(&mut table);
+    merge(&mut merged, table);
+    Ok(merged)
+}
+
+/// Fold deprecated `[boot]` tables into `[splash]` (explicit `[splash]` keys win),
+/// including inside `[os.*]`, `[host.*]` and `[profile.*]` tables.
+pub fn normalize_aliases(table: &mut Table) {
+    if let Some(Value::Table(boot)) = table.remove("boot") {
+        let mut splash = boot;
+        if let Some(Value::Table(existing)) = table.remove("splash") {
+            merge(&mut splash, existing);
+        }
+        table.insert("splash".into(), Value::Table(splash));
+    }
+    for section in ["os", "host", "profile"] {
+        if let Some(Value::Table(nested)) = table.get_mut(section) {
+            for (_, value) in nested.iter_mut() {
+                if let Value::Table(t) = value {
+                    normalize_aliases(t);
+                }
+            }
+        }
+    }
+}
+
+/// Expand `~` and resolve relative paths against the including file's directory.
+fn resolve(base_dir: &Path, path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Ok(home) = std::env::var("HOME") {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+    let p = PathBuf::from(path);
+    if p.is_absolute() {
+        p
+    } else {
+        base_dir.join(p)
+    }
+}
+
+/// Merge `[os.<name>]` and matching `[host."<pattern>"]` tables onto the base,
+/// removing both sections. OS overrides apply first, then host globs in file
+/// order, then an exact hostname match, so the most specific table wins.
+pub fn apply_overrides(mut table: Table, hostname: &str, os: &str) -> Table {
+    let os_tables = table.remove("os");
+    let host_tables = table.remove("host");
+
+    if let Some(Value::Table(mut by_os)) = os_tables {
+        if let Some(Value::Table(t)) = by_os.remove(os) {
+            merge(&mut table, t);
+        }
+    }
+
+    if let Some(Value::Table(by_host)) = host_tables {
+        let mut exact = None;
+        for (pattern, value) in by_host {
+            let Value::Table(t) = value else { continue };
+            if pattern.eq_ignore_ascii_case(hostname) {
+                exact = Some(t);
+            } else if glob_match(&pattern, hostname) {
+                merge(&mut table, t);
+            }
+        }
+        if let Some(t) = exact {
+            merge(&mut table, t);
+        }
+    }
+
+    table
+}
+
+/// Merge the `[profile.<name>]` table selected with `--profile` onto the base,
+/// removing all profile tables. Fails if the profile is not defined.
+pub fn apply_profile(table: &mut Table, name: Option<&str>) -> Result<(), String> {
+    let mut profiles = match table.remove("profile") {
+        Some(Value::Table(t)) => t,
+        _ => Table::new(),
+    };
+    let Some(name) = name else { return Ok(()) };
+
+    match profiles.remove(name) {
+        Some(Value::Table(t)) => {
+            merge(table, t);
+            Ok(())
+        }
+        _ => {
+            let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            let message = match check::suggest(name, known.iter().copied()) {
+                Some(s) => format!("unknown profile \"{}\" (did you mean \"{}\"?)", name, s),
+                None if known.is_empty() => format!("unknown profile \"{}\" (no [profile.*] tables defined)", name),
+                None => format!("unknown profile \"{}\" (defined: {})", name, known.join(", ")),
+            };
+            Err(message)
+        }
+    }
+}
+
+/// Deep-merge `over` into `base`: nested tables merge, everything else replaces.
+pub fn merge(base: &mut Table, over: Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(b)), Value::Table(o)) => merge(b, o),
+            (_, v) => {
+                base.insert(key, v);
+            }
+        }
+    }
+}
+
+/// Shell-style glob supporting `*` and `?`, case-insensitive like hostnames.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode_for(config: &str, hostname: &str) -> Option<String> {
+        let table: Table = toml::from_str(config).unwrap();
+        let table = apply_overrides(table, hostname, "linux");
+        table.get("mode").and_then(Value::as_str).map(String::from)
+    }
+
+    #[test]
+    fn overlapping_host_globs_apply_in_file_order() {
+        let general_first = r#"
+            [host."*"]
+            mode = "default"
+            [host."build*"]
+            mode = "neofetch"
+        "#;
+        let specific_first = r#"
+            [host."build*"]
+            mode = "neofetch"
+            [host."*"]
+            mode = "default"
+        "#;
+        assert_eq!(mode_for(general_first, "buildbox").as_deref(), Some("neofetch"));
+        assert_eq!(mode_for(specific_first, "buildbox").as_deref(), Some("default"));
+    }
+
+    #[test]
+    fn exact_hostname_wins_over_later_globs() {
+        let config = r#"
+            [host."buildbox"]
+            mode = "splash"
+            [host."build*"]
+            mode = "neofetch"
+        "#;
+        assert_eq!(mode_for(config, "buildbox").as_deref(), Some("splash"));
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+use toml_edit::{DocumentMut, TableLike};
+
+/// Rewrite deprecated keys in a config file in place, keeping comments and layout.
+/// Returns a description of each change; the original is kept as `<file>.bak`.
+pub fn migrate(path: &Path) -> Result<Vec<String>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| format!("{}: {}", path.display(), e.message().trim()))?;
+
+    let mut changes = Vec::new();
+    migrate_table(doc.as_table_mut(), "", &mut changes);
+    for section in ["os", "host", "profile"] {
+        if let Some(nested) = doc.get_mut(section).and_then(|i| i.as_table_like_mut()) {
+            for (name, item) in nested.iter_mut() {
+                if let Some(t) = item.as_table_like_mut() {
+                    let prefix = format!("{}.{}.", section, toml_key(name.get()));
+                    migrate_table(t, &prefix, &mut changes);
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    let backup = path.with_extension("toml.bak");
+    std::fs::copy(path, &backup).map_err(|e| format!("cannot write {}: {}", backup.display(), e))?;
+    std::fs::write(path, doc.to_string()).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    Ok(changes)
+}
+
+/// Apply every migration rule to one table of settings.
+fn migrate_table(table: &mut dyn TableLike, prefix: &str, changes: &mut Vec<String>) {
+    rename_boot(table, prefix, changes);
+}
+
+/// `[boot]` → `[splash]`. If both exist, boot keys not already in splash are moved over.
+fn rename_boot(table: &mut dyn TableLike, prefix: &str, changes: &mut Vec<String>) {
+    let Some(boot) = table.remove("boot") else { return };
+
+    match table.get_mut("splash").and_then(|i| i.as_table_like_mut()) {
+        Some(splash) => {
+            if let Some(boot) = boot.as_table_like() {
+                for (key, value) in boot.iter() {
+                    if !splash.contains_key(key) {
+                        splash.insert(key, value.clone());
+                    }
+                }
+            }
+            changes.push(format!("merged [{p}boot] into [{p}splash]", p = prefix));
+        }
+        None => {
+            table.insert("splash", boot);
+            changes.push(format!("renamed [{p}boot] to [{p}splash]", p = prefix));
+        }
+    }
+}
+
+/// Quote a key for display if it is not a bare TOML key.
+fn toml_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key)
+    }
+}
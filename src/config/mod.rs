@@ -0,0 +1,695 @@
+pub mod check;
+mod layer;
+mod migrate;
+mod overrides;
+
+pub use migrate::migrate;
+pub use overrides::Override;
+
+use blaeck::prelude::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub mode: Option<String>,
+    pub color: Option<String>,
+    pub logo: Option<String>,
+    pub logo_file: Option<String>,
+    /// Divider character under the title; overrides the `divider` style's character.
+    pub separator: Option<String>,
+    /// Title template, e.g. `"{user} on {hostname}"`.
+    pub title_format: Option<String>,
+    /// Divider style under the title.
+    pub divider: Option<String>,
+    /// `left`, `right`, `top` or `bottom` of the info.
+    pub logo_position: Option<String>,
+    pub logo_padding: Option<LogoPadding>,
+    /// Vertical alignment of logo and info side by side: `top`, `center` or `bottom`.
+    pub vertical_align: Option<String>,
+    /// Box style: `rounded`, `single`, `double`, `bold`, `ascii` or `none`.
+    pub border: Option<String>,
+    /// What the border wraps: `info` (default) or `all`.
+    pub border_around: Option<String>,
+    /// Title template drawn in the top edge of the border.
+    pub border_title: Option<String>,
+    /// Stack or drop the logo and truncate values to fit the terminal width.
+    pub responsive: Option<bool>,
+    /// `auto` (default), `always` or `never`.
+    pub color_mode: Option<String>,
+    /// Palette swatch: `blocks`, `circles` or any string.
+    pub palette_glyph: Option<String>,
+    /// Times the glyph is repeated per swatch.
+    pub palette_width: Option<u16>,
+    /// Spaces between swatches.
+    pub palette_spacing: Option<u16>,
+    /// `1` or `2` rows of swatches.
+    pub palette_rows: Option<u8>,
+    /// `ansi` (the 16 terminal colors), `256` or `truecolor` gradients.
+    pub palette_colors: Option<String>,
+    /// `info` (under the info) or `logo` (under the logo).
+    pub palette_position: Option<String>,
+    /// Layout keys at the top level apply to every mode.
+    #[serde(flatten)]
+    pub layout: LayoutConfig,
+    pub colors: Option<ColorsConfig>,
+    pub style: Option<StyleConfig>,
+    /// `[format]` section: per-field value templates such as `"{used_gib:.1}/{total_gib} GiB"`,
+    /// or tables that draw the value as a bar.
+    pub format: Option<BTreeMap<String, FieldFormat>>,
+    /// `[when.<field>]` rules that color or hide a field based on its value.
+    pub when: Option<BTreeMap<String, Rules>>,
+    /// Also read from the deprecated `[boot]` alias, which is merged in at load time.
+    pub splash: Option<BootConfig>,
+    /// `[export]` section: font and colors for `--export`.
+    pub export: Option<ExportConfig>,
+    /// `[default]` section: layout overrides for default mode.
+    pub default: Option<LayoutConfig>,
+    /// `[neofetch]` section: layout overrides for neofetch mode.
+    pub neofetch: Option<LayoutConfig>,
+}
+
+/// Keys that control the info column layout. Set at the top level or per mode.
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub palette: Option<bool>,
+    pub label_align: Option<String>,
+    pub label_position: Option<String>,
+    pub value_align: Option<String>,
+    pub field_separator: Option<String>,
+    pub fields: Option<Vec<String>>,
+    pub labels: Option<BTreeMap<String, String>>,
+    /// Hide fields whose value is "Unknown" or empty.
+    pub hide_unknown: Option<bool>,
+    /// Show the title and divider at the top when `fields` has no `Title` entry.
+    pub show_title: Option<bool>,
+    /// Number of side-by-side columns the fields flow into.
+    pub columns: Option<u8>,
+}
+
+impl LayoutConfig {
+    /// Keys set in `over` replace ours; labels merge per field.
+    fn merged_with(&self, over: &LayoutConfig) -> LayoutConfig {
+        let labels = match (&self.labels, &over.labels) {
+            (Some(base), Some(o)) => {
+                let mut m = base.clone();
+                m.extend(o.iter().map(|(k, v)| (k.clone(), v.clone())));
+                Some(m)
+            }
+            (base, o) => o.clone().or_else(|| base.clone()),
+        };
+        LayoutConfig {
+            palette: over.palette.or(self.palette),
+            label_align: over.label_align.clone().or_else(|| self.label_align.clone()),
+            label_position: over.label_position.clone().or_else(|| self.label_position.clone()),
+            value_align: over.value_align.clone().or_else(|| self.value_align.clone()),
+            field_separator: over.field_separator.clone().or_else(|| self.field_separator.clone()),
+            fields: over.fields.clone().or_else(|| self.fields.clone()),
+            labels,
+            hide_unknown: over.hide_unknown.or(self.hide_unknown),
+            show_title: over.show_title.or(self.show_title),
+            columns: over.columns.or(self.columns),
+        }
+    }
+
+    pub fn label_for(&self, key: &str) -> String {
+        self.labels
+            .as_ref()
+            .and_then(|m| m.get(key).cloned())
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct BootConfig {
+    pub image: Option<String>,
+    pub stretch: Option<String>,
+    pub transparency: Option<u8>,
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    pub timeout: Option<u64>,
+    pub star_brightness: Option<u8>,
+    pub render_mode: Option<String>,
+    pub align: Option<String>,
+    pub min_width: Option<u16>,
+    pub min_height: Option<u16>,
+    pub max_width: Option<u16>,
+    pub max_height: Option<u16>,
+    pub entrance: Option<String>,
+    pub exit: Option<String>,
+}
+
+/// How `--export` draws the SVG or HTML. Unset keys use the defaults in `export.rs`.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// CSS font family list, e.g. `"JetBrains Mono, monospace"`.
+    pub font: Option<String>,
+    /// Font size in pixels.
+    pub font_size: Option<u16>,
+    pub background: Option<ColorValue>,
+    /// Text without a color of its own.
+    pub foreground: Option<ColorValue>,
+}
+
+/// One `[when.<field>]` rule. Every condition that is set must hold for it to match;
+/// a rule with no conditions always matches.
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct Rule {
+    /// The field's percentage (or first number in its value) is above this
+    pub above: Option<u64>,
+    pub below: Option<u64>,
+    pub equals: Option<String>,
+    /// The value is "Unknown" or empty
+    pub unknown: Option<bool>,
+    pub color: Option<ColorValue>,
+    pub hide: Option<bool>,
+}
+
+impl Rule {
+    pub fn matches(&self, value: &str, number: Option<f64>) -> bool {
+        let above = self.above.is_none_or(|n| number.is_some_and(|v| v > n as f64));
+        let below = self.below.is_none_or(|n| number.is_some_and(|v| v < n as f64));
+        let equals = self.equals.as_deref().is_none_or(|e| e == value);
+        let unknown = self.unknown.is_none_or(|u| u == is_unknown(value));
+        above && below && equals && unknown
+    }
+}
+
+/// A `[format]` entry: a value template, or a table with display options.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum FieldFormat {
+    Template(String),
+    Display(FieldDisplay),
+}
+
+/// `[format]` table such as `Memory = { style = "bar", width = 20 }`.
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct FieldDisplay {
+    /// `text` (default) or `bar`
+    pub style: Option<String>,
+    /// Bar width in cells
+    pub width: Option<u16>,
+    /// Template shown after the bar; empty for the bar alone
+    pub text: Option<String>,
+    /// Percentages where the bar turns yellow and red
+    pub warn: Option<u64>,
+    pub critical: Option<u64>,
+}
+
+/// A single `[when.<field>]` table or a list of them (`[[when.<field>]]`).
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum Rules {
+    Many(Vec<Rule>),
+    One(Rule),
+}
+
+impl Rules {
+    pub fn iter(&self) -> std::slice::Iter<'_, Rule> {
+        match self {
+            Rules::Many(rules) => rules.iter(),
+            Rules::One(rule) => std::slice::from_ref(rule).iter(),
+        }
+    }
+}
+
+/// `fields` entries that are not fields: `"Title"`, `"---"` spacers and `"# Header"` lines.
+pub fn is_layout_entry(entry: &str) -> bool {
+    entry == "Title" || entry == "---" || entry.starts_with('#')
+}
+
+/// Whether a field value means the info could not be gathered.
+pub fn is_unknown(value: &str) -> bool {
+    value.is_empty() || value == "Unknown"
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub title: Option<ColorValue>,
+    pub label: Option<ColorValue>,
+    pub separator: Option<ColorValue>,
+    pub logo: Option<ColorValue>,
+    pub value: Option<ColorValue>,
+    pub border: Option<ColorValue>,
+}
+
+/// Space around the logo: blank lines above, columns to its left, and the
+/// gap between logo and info (columns side by side, lines when stacked).
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct LogoPadding {
+    pub top: Option<u16>,
+    pub left: Option<u16>,
+    pub gap: Option<u16>,
+}
+
+/// Color and text attributes for a label or value. Unset keys keep the default.
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct TextStyle {
+    pub color: Option<ColorValue>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub dim: Option<bool>,
+}
+
+/// `[style]` section: label/value styles for every field, and per field in `[style.field.<name>]`.
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct StyleConfig {
+    /// `"# Header"` entries in `fields`
+    pub header: Option<TextStyle>,
+    pub label: Option<TextStyle>,
+    pub value: Option<TextStyle>,
+    pub field: Option<BTreeMap<String, FieldStyle>>,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct FieldStyle {
+    pub label: Option<TextStyle>,
+    pub value: Option<TextStyle>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum ColorValue {
+    Named(String),
+    Rgb([u8; 3]),
+}
+
+/// Names accepted by `ColorValue::Named`.
+pub const COLOR_NAMES: &[&str] = &[
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+    "dark_gray", "darkgray", "light_red", "lightred", "light_green", "lightgreen",
+    "light_yellow", "lightyellow", "light_blue", "lightblue", "light_magenta",
+    "lightmagenta", "light_cyan", "lightcyan",
+];
+
+impl ColorValue {
+    pub fn to_color(&self) -> Color {
+        match self {
+            ColorValue::Named(name) => match name.to_lowercase().as_str() {
+                "black" => Color::Black,
+                "red" => Color::Red,
+                "green" => Color::Green,
+                "yellow" => Color::Yellow,
+                "blue" => Color::Blue,
+                "magenta" => Color::Magenta,
+                "cyan" => Color::Cyan,
+                "white" => Color::White,
+                "dark_gray" | "darkgray" => Color::DarkGray,
+                "light_red" | "lightred" => Color::LightRed,
+                "light_green" | "lightgreen" => Color::LightGreen,
+                "light_yellow" | "lightyellow" => Color::LightYellow,
+                "light_blue" | "lightblue" => Color::LightBlue,
+                "light_magenta" | "lightmagenta" => Color::LightMagenta,
+                "light_cyan" | "lightcyan" => Color::LightCyan,
+                _ => Color::White,
+            },
+            ColorValue::Rgb(rgb) => Color::Rgb(rgb[0], rgb[1], rgb[2]),
+        }
+    }
+}
+
+impl Config {
+    /// Load the config file and the selected profile, then apply `BLAECKFETCH_*`
+    /// env vars and `--set` overrides on top (file < profile < env < CLI).
+    /// Problems are printed as warnings.
+    pub fn load(path: Option<&str>, profile: Option<&str>, cli: &[Override]) -> Self {
+        let (config, diags) = Self::load_checked(path, profile, cli);
+        for d in &diags {
+            eprintln!("warning: {}", d);
+        }
+        config
+    }
+
+    /// Load the config and return every problem found along the way.
+    /// Invalid entries are dropped individually; the rest of the config still applies.
+    pub fn load_checked(path: Option<&str>, profile: Option<&str>, cli: &[Override]) -> (Self, Vec<check::Diagnostic>) {
+        let config_path = match path {
+            Some(p) => PathBuf::from(p),
+            None => default_config_path(),
+        };
+        let origin = config_path.display().to_string();
+
+        let mut diags = Vec::new();
+        let mut table = if config_path.exists() {
+            match layer::load_file(&config_path, &mut diags) {
+                Ok(t) => {
+                    let hostname = sysinfo::System::host_name().unwrap_or_default();
+                    layer::apply_overrides(t, &hostname, std::env::consts::OS)
+                }
+                Err(e) => {
+                    diags.push(check::Diagnostic::plain(origin.clone(), e));
+                    toml::Table::new()
+                }
+            }
+        } else {
+            // Only the default path may be missing; a file named on purpose must exist
+            if path.is_some() {
+                diags.push(check::Diagnostic::plain(
+                    "--config",
+                    format!("cannot read {}: not found", origin),
+                ));
+            }
+            toml::Table::new()
+        };
+
+        if let Err(e) = layer::apply_profile(&mut table, profile) {
+            diags.push(check::Diagnostic::plain("--profile", e));
+        }
+
+        layer::merge(&mut table, overrides::from_env(&mut diags));
+        layer::merge(&mut table, overrides::from_args(cli, &mut diags));
+
+        let config = table.try_into().unwrap_or_else(|e: toml::de::Error| {
+            diags.push(check::Diagnostic::plain(origin, e.to_string()));
+            Config::default()
+        });
+        (config, diags)
+    }
+
+    pub fn default_fields() -> Vec<String> {
+        vec![
+            "OS".into(),
+            "Host".into(),
+            "Kernel".into(),
+            "Uptime".into(),
+            "Packages".into(),
+            "Shell".into(),
+            "Resolution".into(),
+            "DE".into(),
+            "WM".into(),
+            "WM Theme".into(),
+            "Terminal".into(),
+            "CPU".into(),
+            "GPU".into(),
+            "Memory".into(),
+            "Disk (/)".into(),
+            "Battery".into(),
+            "Local IP".into(),
+        ]
+    }
+
+    /// Layout for a mode: its `[default]`/`[neofetch]` section over the top-level keys.
+    pub fn layout_for_mode(&self, mode: crate::mode::Mode) -> LayoutConfig {
+        let section = match mode {
+            crate::mode::Mode::Default => self.default.as_ref(),
+            crate::mode::Mode::Neofetch => self.neofetch.as_ref(),
+            crate::mode::Mode::Splash => None,
+        };
+        match section {
+            Some(s) => self.layout.merged_with(s),
+            None => self.layout.clone(),
+        }
+    }
+
+    /// The `[format]` template for a field, if one is set.
+    pub fn format_for(&self, field: &str) -> Option<&str> {
+        match self.format.as_ref()?.get(field)? {
+            FieldFormat::Template(t) => Some(t),
+            FieldFormat::Display(d) => d.text.as_deref(),
+        }
+    }
+
+    /// Display options for a field drawn as a bar (`style = "bar"` in `[format]`).
+    pub fn bar_for(&self, field: &str) -> Option<&FieldDisplay> {
+        match self.format.as_ref()?.get(field)? {
+            FieldFormat::Display(d) if d.style.as_deref() == Some("bar") => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Get splash config ([boot] is folded into [splash] when loading).
+    pub fn splash_config(&self) -> Option<&BootConfig> {
+        self.splash.as_ref()
+    }
+
+    /// Serialize the config back to TOML, omitting unset keys.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_default()
+    }
+
+    /// Get active fields for a given mode.
+    pub fn active_fields_for_mode(&self, mode: crate::mode::Mode) -> Vec<String> {
+        self.layout_for_mode(mode)
+            .fields
+            .unwrap_or_else(|| mode.default_fields())
+    }
+
+    /// Whether to show palette for a given mode.
+    pub fn show_palette_for_mode(&self, mode: crate::mode::Mode) -> bool {
+        self.layout_for_mode(mode)
+            .palette
+            .unwrap_or_else(|| mode.default_palette())
+    }
+}
+
+/// User config path, falling back to a system-wide config in `$XDG_CONFIG_DIRS`
+/// when the user has none.
+pub fn default_config_path() -> PathBuf {
+    let user = dirs_config().join("config.toml");
+    if user.exists() {
+        return user;
+    }
+    crate::xdg::config_dirs()
+        .into_iter()
+        .map(|d| d.join("config.toml"))
+        .find(|p| p.exists())
+        .unwrap_or(user)
+}
+
+fn dirs_config() -> PathBuf {
+    crate::xdg::config_home().unwrap_or_else(|| PathBuf::from(".config/blaeckfetch"))
+}
+
+pub fn generate_default() -> String {
+    r##"# blaeckfetch configuration
+# Place this file at ~/.config/blaeckfetch/config.toml ($XDG_CONFIG_HOME/blaeckfetch),
+# or at /etc/xdg/blaeckfetch/config.toml for a system-wide default.
+
+# Display mode: default (moon + minimal), neofetch (classic layout), splash (animation)
+# mode = "default"
+
+# Color theme: green, cyan, red, magenta, yellow, blue, mono
+# color = "green"
+
+# Logo: moon, apple, linux, ubuntu, arch, debian, fedora, none, auto
+# logo = "auto"
+
+# Custom ASCII art file (overrides logo)
+# logo_file = "~/.config/blaeckfetch/logo.txt"
+
+# Logo position relative to the info: left (default), right, top, bottom
+# logo_position = "left"
+# Vertical alignment of logo and info side by side: top, center, bottom
+# vertical_align = "center"
+# Spacing: blank lines above the logo, columns to its left, and the gap
+# to the info (columns side by side, default 3; lines stacked, default 1)
+# logo_padding = { top = 0, left = 0, gap = 3 }
+
+# Box around the info column: rounded, single, double, bold, ascii, none.
+# border_around = "all" also wraps the logo; border_title goes in the top
+# edge and takes the title_format placeholders.
+# border = "rounded"
+# border_around = "info"
+# border_title = "{hostname}"
+
+# On narrow terminals, stack the logo above the info, then drop it, then
+# cut long values with an ellipsis. Set to false to keep the normal layout.
+# responsive = true
+
+# Colors: auto (only on a terminal, and not when NO_COLOR is set), always
+# or never. Without color the layout is written as plain text.
+# color_mode = "auto"
+
+# Show color palette at bottom
+# palette = true
+
+# Palette look: glyph is blocks, circles or any string, repeated `width`
+# times per color with `spacing` spaces between. colors is ansi (the 16
+# terminal colors), 256 or truecolor (gradients). position is info (under
+# the info) or logo (under the logo).
+# palette_glyph = "blocks"
+# palette_width = 3
+# palette_spacing = 0
+# palette_rows = 2
+# palette_colors = "ansi"
+# palette_position = "info"
+
+# Title template. Placeholders: {user} {hostname} {os} {host} {kernel}
+# {shell} {terminal}
+# title_format = "{user}@{hostname}"
+
+# Divider under the title: dashed (default), solid, double, dotted, bold,
+# ascii or none. A separator character replaces the style's own.
+# divider = "dashed"
+# separator = "-"
+
+# Label alignment: left (default) or right (colons line up)
+# label_align = "right"
+
+# Label position: left (default) or right (value first, then label)
+# label_position = "right"
+
+# Value alignment: left (default) or right (values form a right-aligned column)
+# value_align = "right"
+
+# String between label and value (default: ": ")
+# Use "fill" for a line that fills the gap: OS ──────── MacOS 15.5
+# field_separator = ": "
+# field_separator = " → "
+# field_separator = "fill"
+
+# Flow the fields into side-by-side columns; each column aligns on its own
+# columns = 2
+
+# Fields to display (in order). Remove or comment out entries to hide them.
+# fields = [
+#     "OS",
+#     "Host",
+#     "Kernel",
+#     "Uptime",
+#     "Packages",
+#     "Shell",
+#     "Resolution",
+#     "DE",
+#     "WM",
+#     "WM Theme",
+#     "Terminal",
+#     "CPU",
+#     "GPU",
+#     "Memory",
+#     "Disk (/)",
+#     "Battery",
+#     "Local IP",
+# ]
+
+# Per-mode layout: keys in [default] or [neofetch] override the top-level
+# layout keys (fields, palette, labels, label_align, label_position,
+# value_align, field_separator, hide_unknown, show_title, columns) only in
+# that mode.
+# [neofetch]
+# fields = ["OS", "Host", "Kernel", "CPU", "GPU", "Memory"]
+# palette = true
+#
+# [default]
+# fields = ["OS", "Uptime", "Memory"]
+
+# Custom field labels (rename any field)
+# [labels]
+# "Disk (/)" = "Disk"
+# "Local IP" = "IP"
+
+# Per-field value templates. Run `blaeckfetch --list-fields` for the
+# placeholders each field offers; {value} is always available.
+# Numbers take a width/precision spec like {used_gib:.1} or {percent:>3}.
+# [format]
+# Memory = "{used_gib:.1}/{total_gib} GiB ({percent}%)"
+# Uptime = "{days}d {hours}h"
+#
+# Memory, Disk and Battery can be drawn as a green/yellow/red bar. `text` is
+# shown after it (default: the plain value); warn and critical are percentages.
+# "Disk (/)" = { style = "bar", width = 20, text = "{percent}%", warn = 60, critical = 85 }
+
+# Besides field names, fields can contain "Title" (user@host and divider),
+# "---" (blank line) and "# Some Header" (section header). Without a
+# "Title" entry the title goes first; show_title = false removes it.
+# fields = ["Title", "# Software", "OS", "Kernel", "---", "# Hardware", "CPU", "Memory"]
+# show_title = true
+
+# Hide fields that could not be detected (value "Unknown")
+# hide_unknown = true
+
+# Color or hide a field based on its value. Conditions: above, below
+# (percentage or first number), equals, unknown. Actions: color, hide.
+# Use [[when.<field>]] for several rules; the last matching color wins.
+# [when."Disk (/)"]
+# above = 90
+# color = "red"
+
+# Custom colors (override theme)
+# Values: named color string or RGB array [r, g, b]
+# Named colors: black, red, green, yellow, blue, magenta, cyan, white,
+#               dark_gray, light_red, light_green, light_yellow,
+#               light_blue, light_magenta, light_cyan
+# [colors]
+# title = "cyan"
+# label = [100, 200, 255]
+# separator = "dark_gray"
+# logo = "green"
+# value = "white"
+# border = "dark_gray"
+
+# Label/value styles: color, bold, italic, underline, dim.
+# [style] applies to every field, [style.field.<name>] to one.
+# [style]
+# header = { underline = true }
+# label = { bold = true }
+# value = { italic = false }
+#
+# [style.field.Memory]
+# value = { color = "yellow", bold = true }
+
+# Font and page colors for --export out.svg / out.html
+# [export]
+# font = "Menlo, Consolas, 'DejaVu Sans Mono', monospace"   # CSS font family
+# font_size = 14                        # Pixels
+# background = [30, 30, 30]
+# foreground = [229, 229, 229]          # Text without a color of its own
+
+# Share settings between machines: included files are loaded first,
+# then this file's own keys override them. Paths are relative to this file.
+# include = ["common.toml"]
+
+# Per-OS overrides (macos, linux), merged onto everything above
+# [os.macos]
+# logo = "apple"
+
+# Per-host overrides, matched against the hostname (* and ? globs).
+# Applied after [os.*]; an exact hostname match wins over globs.
+# [host."buildbox-*"]
+# mode = "neofetch"
+
+# Named profiles, selected with --profile <name> and merged over everything above
+# [profile.motd]
+# mode = "neofetch"
+# palette = false
+#
+# [profile.login]
+# mode = "splash"
+
+# Splash mode (retro console animation)
+# Run with: blaeckfetch --splash
+# Use --center or --left to control alignment.
+# Without an image, shows a procedural starfield with earth and moon.
+# [splash]
+# image = "~/.config/blaeckfetch/space.png"   # Background image (PNG/JPEG)
+# stretch = "fill"                         # fill (stretch), fit (letterbox), or crop
+# transparency = 0                         # 0 = opaque black bg, 1-255 = dark pixels become transparent
+# width = 68                               # Canvas width in columns (default: auto from image or 68)
+# height = 23                              # Canvas height in rows (default: auto from image or 23)
+# timeout = 120                            # Seconds before auto-closing (triggers collapse animation)
+# star_brightness = 30                     # Star twinkle detection threshold (0-255)
+# render_mode = "auto"                     # auto (detect terminal), image (force iTerm2), ascii (force half-block)
+# align = "left"                           # left, center, or right (CLI --left/--center/--right override)
+# min_width = 40                           # Minimum canvas width in columns
+# min_height = 12                          # Minimum canvas height in rows
+# max_width = 120                          # Maximum canvas width in columns
+# max_height = 40                          # Maximum canvas height in rows
+# entrance = "slow"                        # Entrance animation: slow (~1.2s), fast (~400ms), instant
+# exit = "slow"                            # Exit animation: slow (~400ms), fast (~200ms), instant
+"##
+    .to_string()
+}
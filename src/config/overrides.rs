@@ -0,0 +1,230 @@
+use toml::{Table, Value};
+use toml_edit::ImDocument;
+
+use super::check::{self, Diagnostic, Kind};
+use super::layer;
+use super::Config;
+
+const ENV_PREFIX: &str = "BLAECKFETCH_";
+
+/// Settings from `BLAECKFETCH_*` environment variables, e.g.
+/// `BLAECKFETCH_SPLASH_TIMEOUT=10` or `BLAECKFETCH_FIELDS=OS,CPU`.
+pub fn from_env(diags: &mut Vec<Diagnostic>) -> Table {
+    // vars() panics on any variable that is not UTF-8, even unrelated ones
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for (key, value) in std::env::vars_os() {
+        let Some(var) = key.to_str().filter(|k| k.starts_with(ENV_PREFIX)) else { continue };
+        match value.into_string() {
+            Ok(raw) => vars.push((var.to_string(), raw)),
+            Err(_) => diags.push(Diagnostic::plain(var, "value is not valid UTF-8")),
+        }
+    }
+    vars.sort();
+
+    let mut table = Table::new();
+    for (var, raw) in vars {
+        let name = var[ENV_PREFIX.len()..].to_lowercase();
+        match env_key(&name) {
+            Some(path) => apply(&mut table, &path, &raw, &format!("{}={}", var, raw), diags),
+            None => diags.push(Diagnostic::plain(var, "does not name a config key")),
+        }
+    }
+    table
+}
+
+/// A `key=value` setting from the command line, with the argument it came from.
+pub struct Override {
+    origin: String,
+    setting: String,
+}
+
+impl Override {
+    /// A generic `--set key=value` argument.
+    pub fn set(setting: &str) -> Self {
+        Self {
+            origin: format!("--set {}", setting),
+            setting: setting.to_string(),
+        }
+    }
+
+    /// A dedicated flag such as `--color red` that maps onto a config key.
+    pub fn flag(flag: &str, key: &str, value: &str) -> Self {
+        Self {
+            origin: if value.is_empty() { flag.to_string() } else { format!("{} {}", flag, value) },
+            setting: format!("{}={}", key, value),
+        }
+    }
+}
+
+/// Settings from command-line overrides, applied in order.
+pub fn from_args(args: &[Override], diags: &mut Vec<Diagnostic>) -> Table {
+    let mut table = Table::new();
+    for arg in args {
+        match arg.setting.split_once('=') {
+            Some((key, raw)) => {
+                let path = key_path(key);
+                apply(&mut table, &path, raw.trim(), &arg.origin, diags);
+            }
+            None => diags.push(Diagnostic::plain(arg.origin.as_str(), "expected key=value")),
+        }
+    }
+    table
+}
+
+/// Split a `--set` key into its path, with TOML dotted-key rules so quoted
+/// segments such as `format."Disk (/)".text` work as in a file. Keys TOML does
+/// not accept, such as `labels.Local IP`, are split on every `.`.
+fn key_path(key: &str) -> Vec<String> {
+    match toml_edit::Key::parse(key.trim()) {
+        Ok(keys) => keys.iter().map(|k| k.get().to_string()).collect(),
+        Err(_) => key.trim().split('.').map(String::from).collect(),
+    }
+}
+
+/// Build a one-line TOML document for the override, validate it against the
+/// schema and merge whatever is valid.
+fn apply(table: &mut Table, path: &[String], raw: &str, origin: &str, diags: &mut Vec<Diagnostic>) {
+    let keys: Vec<&str> = path.iter().map(String::as_str).collect();
+    let kind = check::kind_at(&keys);
+    let dotted: Vec<String> = path.iter().map(|k| Value::String(k.clone()).to_string()).collect();
+    let line = format!("{} = {}", dotted.join("."), encode(raw, kind));
+
+    let doc = match ImDocument::parse(line.as_str()) {
+        Ok(d) => d,
+        Err(e) => {
+            diags.push(Diagnostic::plain(origin, e.message().lines().next().unwrap_or("invalid value")));
+            return;
+        }
+    };
+    let mut parsed: Table = toml::from_str(&line).unwrap_or_default();
+    let mut invalid = Vec::new();
+    for p in check::check_settings(&doc) {
+        diags.push(Diagnostic::plain(origin, p.message));
+        invalid.push(p.path);
+    }
+    check::remove_paths(&mut parsed, invalid);
+    layer::normalize_aliases(&mut parsed);
+    layer::merge(table, parsed);
+}
+
+/// Render a raw override string as a TOML value of the expected kind.
+/// Anything that does not fit is passed as a string so the schema check
+/// reports a type error instead of a syntax error.
+fn encode(raw: &str, kind: Option<&Kind>) -> String {
+    let quoted = || Value::String(raw.to_string()).to_string();
+    match kind {
+        Some(Kind::Bool) => match raw.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => "true".into(),
+            "false" | "no" | "off" | "0" => "false".into(),
+            _ => quoted(),
+        },
+        Some(Kind::UInt(_)) => match raw.parse::<i64>() {
+            Ok(n) => n.to_string(),
+            Err(_) => quoted(),
+        },
+        Some(Kind::Color) if raw.contains(',') => {
+            let parts: Option<Vec<i64>> = raw
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|p| p.trim().parse().ok())
+                .collect();
+            match parts {
+                Some(p) => format!("[{}]", p.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(", ")),
+                None => quoted(),
+            }
+        }
+        Some(Kind::List(inner)) => {
+            let items: Vec<String> = raw
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|p| p.trim().trim_matches('"'))
+                .filter(|p| !p.is_empty())
+                .map(|p| encode(p, Some(inner)))
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        Some(Kind::Table(_) | Kind::Map(_) | Kind::FieldMap(_)) if raw.starts_with('{') => raw.to_string(),
+        // A template like "{percent}%" also starts with a brace; only an inline table has `=`
+        Some(Kind::OrTable(..)) if raw.starts_with('{') && raw.contains('=') => raw.to_string(),
+        _ => quoted(),
+    }
+}
+
+/// Map an env var suffix such as `splash_timeout` or `labels_local_ip` onto a
+/// config path by walking the schema, since key names themselves contain `_`.
+fn env_key(name: &str) -> Option<Vec<String>> {
+    let mut path = Vec::new();
+    let mut kind: &Kind = check::kind_at(&[])?;
+    let mut rest = name;
+
+    while !rest.is_empty() {
+        if let Kind::OneOrList(inner) | Kind::OrTable(_, inner) = kind {
+            kind = inner;
+        }
+        let candidates: Vec<(String, String)> = match kind {
+            Kind::Table(groups) => groups
+                .iter()
+                .flat_map(|g| g.iter())
+                .map(|(k, _)| (k.to_string(), k.to_string()))
+                .collect(),
+            Kind::FieldMap(_) => Config::default_fields()
+                .into_iter()
+                .map(|f| (env_name(&f), f))
+                .collect(),
+            Kind::Map(_) => vec![(rest.to_string(), rest.to_string())],
+            _ => return None,
+        };
+
+        // Longest key that matches the whole remainder or is followed by `_`
+        let (matched, key) = candidates
+            .into_iter()
+            .filter(|(env, _)| rest == env || rest.starts_with(&format!("{}_", env)))
+            .max_by_key(|(env, _)| env.len())?;
+
+        path.push(key);
+        let keys: Vec<&str> = path.iter().map(String::as_str).collect();
+        kind = check::kind_at(&keys)?;
+        rest = rest[matched.len()..].trim_start_matches('_');
+    }
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+/// Field name as it appears in an env var: `Disk (/)` → `disk`, `Local IP` → `local_ip`.
+fn env_name(field: &str) -> String {
+    let mut out = String::new();
+    for c in field.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_key_with_quoted_segment() {
+        let mut diags = Vec::new();
+        let table = from_args(&[Override::set("labels.\"Disk (/)\"=Storage")], &mut diags);
+        assert!(diags.is_empty());
+        let label = table["labels"].get("Disk (/)").and_then(Value::as_str);
+        assert_eq!(label, Some("Storage"));
+    }
+
+    #[test]
+    fn set_key_with_bare_spaces() {
+        let mut diags = Vec::new();
+        let table = from_args(&[Override::set("labels.Local IP=LAN")], &mut diags);
+        assert!(diags.is_empty());
+        assert_eq!(table["labels"].get("Local IP").and_then(Value::as_str), Some("LAN"));
+    }
+}
@@ -0,0 +1,459 @@
+use blaeck::prelude::Color;
+use std::path::Path;
+use unicode_width::UnicodeWidthChar;
+
+use crate::config::{ColorValue, Config};
+
+/// File extensions `--export` can write.
+pub const FORMATS: &[&str] = &["svg", "html"];
+
+const DEFAULT_FONT: &str = "Menlo, Consolas, 'DejaVu Sans Mono', monospace";
+const DEFAULT_FONT_SIZE: u16 = 14;
+const DEFAULT_BACKGROUND: Rgb = (30, 30, 30);
+const DEFAULT_FOREGROUND: Rgb = (229, 229, 229);
+/// Cell size relative to the font size: a typical monospace advance, and the
+/// line height. Full blocks are drawn as rectangles of exactly this size, so
+/// the moon and palette have no gaps whatever the font.
+const CELL_WIDTH: f32 = 0.6;
+const LINE_HEIGHT: f32 = 1.2;
+/// Margin around the text, in columns and lines.
+const PAD_COLUMNS: f32 = 2.0;
+const PAD_LINES: f32 = 1.0;
+
+type Rgb = (u8, u8, u8);
+
+/// xterm's default 16 colors, which ANSI color names are drawn with.
+const ANSI: [Rgb; 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Svg,
+    Html,
+}
+
+impl Format {
+    /// Format from the file extension.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("svg") => Ok(Format::Svg),
+            Some("html" | "htm") => Ok(Format::Html),
+            _ => Err(format!(
+                "cannot export to {}: the file name must end in .{}",
+                path.display(),
+                FORMATS.join(" or .")
+            )),
+        }
+    }
+}
+
+/// Page settings from `[export]`.
+struct Page {
+    font: String,
+    font_size: f32,
+    background: Rgb,
+    foreground: Rgb,
+}
+
+impl Page {
+    fn from_config(cfg: &Config) -> Self {
+        let export = cfg.export.as_ref();
+        let rgb = |c: Option<&ColorValue>, default| c.and_then(|c| rgb(c.to_color())).unwrap_or(default);
+        Page {
+            font: export.and_then(|e| e.font.clone()).unwrap_or_else(|| DEFAULT_FONT.into()),
+            font_size: export.and_then(|e| e.font_size).unwrap_or(DEFAULT_FONT_SIZE).max(1) as f32,
+            background: rgb(export.and_then(|e| e.background.as_ref()), DEFAULT_BACKGROUND),
+            foreground: rgb(export.and_then(|e| e.foreground.as_ref()), DEFAULT_FOREGROUND),
+        }
+    }
+}
+
+/// Text attributes of a run, as set by SGR escapes.
+#[derive(Clone, Copy, PartialEq, Default)]
+struct Style {
+    fg: Option<Rgb>,
+    bg: Option<Rgb>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+/// Text in one style, starting at column `col`.
+struct Run {
+    col: usize,
+    width: usize,
+    text: String,
+    style: Style,
+}
+
+/// Convert rendered ANSI output to an SVG or HTML document.
+pub fn document(ansi: &str, format: Format, cfg: &Config) -> String {
+    let lines = parse(ansi);
+    let page = Page::from_config(cfg);
+    match format {
+        Format::Svg => svg(&lines, &page),
+        Format::Html => html(&lines, &page),
+    }
+}
+
+/// Split into lines of styled runs. Only SGR escapes matter; cursor movement
+/// and other sequences from the renderer are skipped.
+fn parse(ansi: &str) -> Vec<Vec<Run>> {
+    let mut lines: Vec<Vec<Run>> = vec![Vec::new()];
+    let mut style = Style::default();
+    let mut col = 0;
+    let mut chars = ansi.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                Some('[') => {
+                    let mut params = String::new();
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            if c == 'm' {
+                                apply_sgr(&mut style, &params);
+                            }
+                            break;
+                        }
+                        params.push(c);
+                    }
+                }
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\r' => {}
+            '\n' => {
+                lines.push(Vec::new());
+                col = 0;
+            }
+            c => {
+                let width = c.width().unwrap_or(0);
+                let line = lines.last_mut().expect("always one line");
+                match line.last_mut() {
+                    Some(run) if run.style == style && run.col + run.width == col => {
+                        run.text.push(c);
+                        run.width += width;
+                    }
+                    _ => line.push(Run {
+                        col,
+                        width,
+                        text: c.to_string(),
+                        style,
+                    }),
+                }
+                col += width;
+            }
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.iter().all(|r| r.text.trim().is_empty() && r.style.bg.is_none())) {
+        lines.pop();
+    }
+    lines
+}
+
+fn apply_sgr(style: &mut Style, params: &str) {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => (style.bold, style.dim) = (false, false),
+            23 => style.italic = false,
+            24 => style.underline = false,
+            n @ 30..=37 => style.fg = Some(ANSI[(n - 30) as usize]),
+            n @ 90..=97 => style.fg = Some(ANSI[(n - 90 + 8) as usize]),
+            39 => style.fg = None,
+            n @ 40..=47 => style.bg = Some(ANSI[(n - 40) as usize]),
+            n @ 100..=107 => style.bg = Some(ANSI[(n - 100 + 8) as usize]),
+            49 => style.bg = None,
+            n @ (38 | 48) => {
+                let color = match codes.get(i + 1) {
+                    Some(5) => {
+                        i += 2;
+                        codes.get(i).map(|&n| indexed(n as u8))
+                    }
+                    Some(2) => {
+                        i += 4;
+                        match codes.get(i - 2..=i) {
+                            Some(&[r, g, b]) => Some((r as u8, g as u8, b as u8)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if n == 38 {
+                    style.fg = color;
+                } else {
+                    style.bg = color;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// RGB for a 256-color palette index: the 16 ANSI colors, the 6x6x6 cube,
+/// then the gray ramp.
+fn indexed(n: u8) -> Rgb {
+    match n {
+        0..=15 => ANSI[n as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + 40 * v };
+            let i = n - 16;
+            (level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + 10 * (n - 232);
+            (v, v, v)
+        }
+    }
+}
+
+/// RGB for a blaeck color, or None for the terminal default.
+fn rgb(c: Color) -> Option<Rgb> {
+    let index = match c {
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(n) => n,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::White | Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        _ => return None,
+    };
+    Some(indexed(index))
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Text color of a run; dim text is drawn halfway to the background.
+fn foreground(style: &Style, page: &Page) -> Rgb {
+    let (r, g, b) = style.fg.unwrap_or(page.foreground);
+    if !style.dim {
+        return (r, g, b);
+    }
+    let (br, bg, bb) = page.background;
+    let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+    (mix(r, br), mix(g, bg), mix(b, bb))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Split a run into pieces of full blocks and other text, with their columns.
+fn pieces(run: &Run) -> Vec<(bool, usize, usize, String)> {
+    let mut pieces: Vec<(bool, usize, usize, String)> = Vec::new();
+    let mut col = run.col;
+    for c in run.text.chars() {
+        let block = c == '█';
+        let width = c.width().unwrap_or(0);
+        match pieces.last_mut() {
+            Some((b, _, w, text)) if *b == block => {
+                text.push(c);
+                *w += width;
+            }
+            _ => pieces.push((block, col, width, c.to_string())),
+        }
+        col += width;
+    }
+    pieces
+}
+
+fn svg(lines: &[Vec<Run>], page: &Page) -> String {
+    let cw = page.font_size * CELL_WIDTH;
+    let lh = page.font_size * LINE_HEIGHT;
+    let columns = lines.iter().filter_map(|l| l.last()).map(|r| r.col + r.width).max().unwrap_or(0);
+    let width = (columns as f32 + 2.0 * PAD_COLUMNS) * cw;
+    let height = (lines.len() as f32 + 2.0 * PAD_LINES) * lh;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" viewBox=\"0 0 {w:.1} {h:.1}\">\n",
+        w = width,
+        h = height
+    );
+    out.push_str(&format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n", hex(page.background)));
+    out.push_str(&format!(
+        "<g font-family=\"{}\" font-size=\"{}\" xml:space=\"preserve\">\n",
+        escape(&page.font),
+        page.font_size
+    ));
+
+    for (row, line) in lines.iter().enumerate() {
+        let top = (row as f32 + PAD_LINES) * lh;
+        // Baseline for a line box of LINE_HEIGHT with the usual 0.8em ascent
+        let baseline = top + (lh - page.font_size) / 2.0 + page.font_size * 0.8;
+        for run in line {
+            let x = |col: usize| (col as f32 + PAD_COLUMNS) * cw;
+            if let Some(bg) = run.style.bg {
+                out.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                    x(run.col),
+                    top,
+                    run.width as f32 * cw,
+                    lh,
+                    hex(bg)
+                ));
+            }
+            let fill = hex(foreground(&run.style, page));
+            for (block, col, width, text) in pieces(run) {
+                if block {
+                    out.push_str(&format!(
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                        x(col),
+                        top,
+                        width as f32 * cw,
+                        lh,
+                        fill
+                    ));
+                    continue;
+                }
+                if text.trim().is_empty() && !run.style.underline {
+                    continue;
+                }
+                let mut attrs = format!("fill=\"{}\"", fill);
+                if run.style.bold {
+                    attrs.push_str(" font-weight=\"bold\"");
+                }
+                if run.style.italic {
+                    attrs.push_str(" font-style=\"italic\"");
+                }
+                if run.style.underline {
+                    attrs.push_str(" text-decoration=\"underline\"");
+                }
+                // textLength pins the text to its cells if the font is not
+                // quite CELL_WIDTH wide
+                out.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" textLength=\"{:.1}\" lengthAdjust=\"spacingAndGlyphs\" {}>{}</text>\n",
+                    x(col),
+                    baseline,
+                    width as f32 * cw,
+                    attrs,
+                    escape(&text)
+                ));
+            }
+        }
+    }
+
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+fn html(lines: &[Vec<Run>], page: &Page) -> String {
+    let mut body = String::new();
+    for line in lines {
+        let mut col = 0;
+        for run in line {
+            body.push_str(&" ".repeat(run.col.saturating_sub(col)));
+            col = run.col + run.width;
+
+            let mut css = Vec::new();
+            if run.style.fg.is_some() || run.style.dim {
+                css.push(format!("color:{}", hex(foreground(&run.style, page))));
+            }
+            if let Some(bg) = run.style.bg {
+                css.push(format!("background:{}", hex(bg)));
+            }
+            if run.style.bold {
+                css.push("font-weight:bold".into());
+            }
+            if run.style.italic {
+                css.push("font-style:italic".into());
+            }
+            if run.style.underline {
+                css.push("text-decoration:underline".into());
+            }
+
+            let mut text = String::new();
+            for (block, _, width, piece) in pieces(run) {
+                if block {
+                    // A cell-sized box, so blocks meet across lines
+                    text.push_str(&format!(
+                        "<span class=\"b\" style=\"width:{}ch;background:{}\"></span>",
+                        width,
+                        hex(foreground(&run.style, page))
+                    ));
+                } else {
+                    text.push_str(&escape(&piece));
+                }
+            }
+            if css.is_empty() {
+                body.push_str(&text);
+            } else {
+                body.push_str(&format!("<span style=\"{}\">{}</span>", css.join(";"), text));
+            }
+        }
+        body.push('\n');
+    }
+    body.pop();
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>blaeckfetch</title>
+<style>
+body {{ margin: 0; background: {bg}; }}
+pre {{ margin: 0; padding: {py}em {px}ch; font-family: {font}; font-size: {size}px; line-height: {lh}; color: {fg}; background: {bg}; }}
+.b {{ display: inline-block; height: {lh}em; vertical-align: top; }}
+</style>
+</head>
+<body>
+<pre>{body}</pre>
+</body>
+</html>
+",
+        bg = hex(page.background),
+        fg = hex(page.foreground),
+        // Entities are not decoded in <style>; only keep it from closing early
+        font = page.font.replace('<', ""),
+        size = page.font_size,
+        lh = LINE_HEIGHT,
+        px = PAD_COLUMNS,
+        py = PAD_LINES * LINE_HEIGHT,
+        body = body
+    )
+}
@@ -0,0 +1,332 @@
+use crate::info::SystemInfo;
+use crate::text;
+
+/// A value a field exposes to `[format]` templates.
+pub enum Arg {
+    Int(u64),
+    Float(f64),
+    Str(String),
+}
+
+/// Placeholders each field exposes, with a description. Every field also has `{value}`.
+pub const PLACEHOLDERS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Title",
+        &[
+            ("user", "login name"),
+            ("hostname", "machine hostname"),
+            ("os", "the OS field"),
+            ("host", "the Host field"),
+            ("kernel", "the Kernel field"),
+            ("shell", "the Shell field"),
+            ("terminal", "the Terminal field"),
+        ],
+    ),
+    ("OS", &[("name", "OS name and version without the architecture"), ("arch", "CPU architecture, e.g. arm64")]),
+    ("Host", &[]),
+    ("Kernel", &[]),
+    (
+        "Uptime",
+        &[
+            ("days", "whole days"),
+            ("hours", "hours past the last whole day (0-23)"),
+            ("mins", "minutes past the last whole hour (0-59)"),
+            ("secs", "seconds past the last whole minute (0-59)"),
+            ("total_hours", "total uptime in hours"),
+            ("total_secs", "total uptime in seconds"),
+        ],
+    ),
+    ("Packages", &[("total", "number of packages across all managers")]),
+    ("Shell", &[("name", "shell name, e.g. zsh"), ("version", "shell version, if known")]),
+    ("Resolution", &[]),
+    ("DE", &[]),
+    ("WM", &[]),
+    ("WM Theme", &[]),
+    ("Terminal", &[]),
+    ("CPU", &[("model", "CPU brand string"), ("cores", "physical core count")]),
+    ("GPU", &[]),
+    (
+        "Memory",
+        &[
+            ("used_mib", "used memory in MiB"),
+            ("total_mib", "total memory in MiB"),
+            ("free_mib", "available memory in MiB"),
+            ("used_gib", "used memory in GiB (decimal)"),
+            ("total_gib", "total memory in GiB (decimal)"),
+            ("free_gib", "available memory in GiB (decimal)"),
+            ("percent", "used memory as a whole percentage"),
+        ],
+    ),
+    (
+        "Disk (/)",
+        &[
+            ("used_gib", "used space in GiB (decimal)"),
+            ("total_gib", "total space in GiB (decimal)"),
+            ("free_gib", "available space in GiB (decimal)"),
+            ("percent", "used space as a whole percentage"),
+        ],
+    ),
+    ("Battery", &[("percent", "charge as a whole percentage"), ("status", "e.g. Charging, Discharging, Full")]),
+    ("Local IP", &[]),
+];
+
+/// Placeholders for a field, not counting `{value}`.
+pub fn placeholders(field: &str) -> &'static [(&'static str, &'static str)] {
+    PLACEHOLDERS
+        .iter()
+        .find(|(f, _)| *f == field)
+        .map(|(_, p)| *p)
+        .unwrap_or(&[])
+}
+
+/// Values for every placeholder the field exposes.
+/// Placeholders whose data is unavailable (e.g. disk stats failed) are left out.
+pub fn args(info: &SystemInfo, field: &str, value: &str) -> Vec<(&'static str, Arg)> {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+    let mut args = vec![("value", Arg::Str(value.to_string()))];
+    match field {
+        "Title" => {
+            for (name, v) in [
+                ("user", &info.user),
+                ("hostname", &info.hostname),
+                ("os", &info.os),
+                ("host", &info.host),
+                ("kernel", &info.kernel),
+                ("shell", &info.shell),
+                ("terminal", &info.terminal),
+            ] {
+                args.push((name, Arg::Str(v.clone())));
+            }
+        }
+        "OS" => {
+            let arch = sysinfo::System::cpu_arch().unwrap_or_default();
+            let name = value
+                .strip_suffix(arch.as_str())
+                .map(str::trim_end)
+                .unwrap_or(value);
+            args.push(("name", Arg::Str(name.to_string())));
+            args.push(("arch", Arg::Str(arch)));
+        }
+        "Uptime" => {
+            let s = info.uptime_secs;
+            args.push(("days", Arg::Int(s / 86400)));
+            args.push(("hours", Arg::Int((s % 86400) / 3600)));
+            args.push(("mins", Arg::Int((s % 3600) / 60)));
+            args.push(("secs", Arg::Int(s % 60)));
+            args.push(("total_hours", Arg::Int(s / 3600)));
+            args.push(("total_secs", Arg::Int(s)));
+        }
+        "Packages" => {
+            let total: u64 = value
+                .split(", ")
+                .filter_map(|p| p.split_whitespace().next()?.parse::<u64>().ok())
+                .sum();
+            args.push(("total", Arg::Int(total)));
+        }
+        "Shell" => {
+            let (name, version) = value.split_once(' ').unwrap_or((value, ""));
+            args.push(("name", Arg::Str(name.to_string())));
+            args.push(("version", Arg::Str(version.to_string())));
+        }
+        "CPU" => {
+            // "<brand> (<cores>)"
+            let (model, cores) = match value.rsplit_once(" (") {
+                Some((m, c)) => (m, c.trim_end_matches(')').parse::<u64>().ok()),
+                None => (value, None),
+            };
+            args.push(("model", Arg::Str(model.to_string())));
+            if let Some(c) = cores {
+                args.push(("cores", Arg::Int(c)));
+            }
+        }
+        "Memory" if info.memory_total > 0 => {
+            let used = info.memory_used;
+            let total = info.memory_total;
+            let free = total.saturating_sub(used);
+            args.push(("used_mib", Arg::Int(used / 1024 / 1024)));
+            args.push(("total_mib", Arg::Int(total / 1024 / 1024)));
+            args.push(("free_mib", Arg::Int(free / 1024 / 1024)));
+            args.push(("used_gib", Arg::Float(used as f64 / GIB)));
+            args.push(("total_gib", Arg::Float(total as f64 / GIB)));
+            args.push(("free_gib", Arg::Float(free as f64 / GIB)));
+            args.push(("percent", Arg::Int(((used as f64 / total as f64) * 100.0).round() as u64)));
+        }
+        "Disk (/)" => {
+            if let Some((total, available)) = info.disk_space.filter(|(t, _)| *t > 0) {
+                let used = total.saturating_sub(available);
+                args.push(("used_gib", Arg::Float(used as f64 / GIB)));
+                args.push(("total_gib", Arg::Float(total as f64 / GIB)));
+                args.push(("free_gib", Arg::Float(available as f64 / GIB)));
+                args.push(("percent", Arg::Int(((used as f64 / total as f64) * 100.0).round() as u64)));
+            }
+        }
+        "Battery" => {
+            let (percent, status) = value.split_once(' ').unwrap_or((value, ""));
+            if let Ok(p) = percent.trim_end_matches('%').parse::<u64>() {
+                args.push(("percent", Arg::Int(p)));
+                args.push(("status", Arg::Str(status.trim_matches(|c| c == '(' || c == ')').to_string())));
+            }
+        }
+        _ => {}
+    }
+    args
+}
+
+/// A field's `{percent}` placeholder, if it has one right now.
+pub fn percent(info: &SystemInfo, field: &str, value: &str) -> Option<u64> {
+    args(info, field, value).into_iter().find_map(|(name, arg)| match (name, arg) {
+        ("percent", Arg::Int(n)) => Some(n),
+        _ => None,
+    })
+}
+
+/// The number `[when]` thresholds compare against: the field's `{percent}`
+/// if it has one, otherwise the first number in the value.
+pub fn number(info: &SystemInfo, field: &str, value: &str) -> Option<f64> {
+    percent(info, field, value).map(|n| n as f64).or_else(|| {
+        let start = value.find(|c: char| c.is_ascii_digit())?;
+        let digits: String = value[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        digits.trim_end_matches('.').parse().ok()
+    })
+}
+
+/// Format a field's value with its template, falling back to the plain value
+/// if the template references data this field does not have right now.
+pub fn apply(info: &SystemInfo, field: &str, value: &str, template: &str) -> String {
+    let args = args(info, field, value);
+    render(template, &args).unwrap_or_else(|_| value.to_string())
+}
+
+/// Expand `{name}` and `{name:spec}` placeholders. `spec` is `[<>^][width][.precision]`.
+/// `{{` and `}}` are literal braces. Floats without a precision show one decimal.
+pub fn render(template: &str, args: &[(&str, Arg)]) -> Result<String, String> {
+    let mut out = String::new();
+    for piece in parse(template)? {
+        match piece {
+            Piece::Text(t) => out.push_str(&t),
+            Piece::Placeholder { name, spec } => {
+                let arg = args
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, a)| a)
+                    .ok_or_else(|| format!("no value for {{{}}}", name))?;
+                out.push_str(&format_arg(arg, &spec)?);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Placeholder names used in a template, for validation. Fails on a
+/// malformed template or format spec.
+pub fn placeholders_in(template: &str) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    for piece in parse(template)? {
+        if let Piece::Placeholder { name, spec } = piece {
+            parse_spec(&spec)?;
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+enum Piece {
+    Text(String),
+    Placeholder { name: String, spec: String },
+}
+
+fn parse(template: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err("unclosed `{`".into()),
+                    }
+                }
+                let (name, spec) = inner.split_once(':').unwrap_or((&inner, ""));
+                if name.is_empty() {
+                    return Err("empty placeholder `{}`".into());
+                }
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(Piece::Placeholder {
+                    name: name.trim().to_string(),
+                    spec: spec.to_string(),
+                });
+            }
+            '}' => return Err("unmatched `}` (use `}}` for a literal brace)".into()),
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// Alignment, width and precision of a `[<>^][width][.precision]` spec.
+fn parse_spec(spec: &str) -> Result<(Option<char>, usize, Option<usize>), String> {
+    let (align, rest) = match spec.chars().next() {
+        Some(c @ ('<' | '>' | '^')) => (Some(c), &spec[1..]),
+        _ => (None, spec),
+    };
+    let (width, precision) = match rest.split_once('.') {
+        Some((w, p)) => (w, Some(p.parse::<usize>().map_err(|_| format!("bad precision in `{}`", spec))?)),
+        None => (rest, None),
+    };
+    let width: usize = if width.is_empty() {
+        0
+    } else {
+        width.parse().map_err(|_| format!("bad width in `{}`", spec))?
+    };
+    Ok((align, width, precision))
+}
+
+fn format_arg(arg: &Arg, spec: &str) -> Result<String, String> {
+    let (align, width, precision) = parse_spec(spec)?;
+
+    let s = match arg {
+        Arg::Int(n) => match precision {
+            Some(p) => format!("{:.*}", p, *n as f64),
+            None => n.to_string(),
+        },
+        Arg::Float(f) => match precision {
+            Some(p) => format!("{:.*}", p, f),
+            None => {
+                let s = format!("{:.1}", f);
+                s.strip_suffix(".0").map(String::from).unwrap_or(s)
+            }
+        },
+        Arg::Str(s) => match precision {
+            Some(p) => s.chars().take(p).collect(),
+            None => s.clone(),
+        },
+    };
+
+    Ok(match align.unwrap_or(if matches!(arg, Arg::Str(_)) { '<' } else { '>' }) {
+        '<' => text::pad_right(&s, width),
+        '^' => text::pad_center(&s, width),
+        _ => text::pad_left(&s, width),
+    })
+}
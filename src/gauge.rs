@@ -0,0 +1,72 @@
+use blaeck::prelude::Color;
+
+use crate::config::FieldDisplay;
+use crate::field;
+use crate::info::SystemInfo;
+
+/// Bar width when `[format]` does not set one.
+pub const DEFAULT_WIDTH: usize = 10;
+/// Percentages at which a bar turns yellow and red.
+const WARN: u64 = 60;
+const CRITICAL: u64 = 85;
+
+/// How full a bar is drawn and how alarming its color.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Ok,
+    Warn,
+    Critical,
+}
+
+impl Level {
+    pub fn color(self) -> Color {
+        match self {
+            Level::Ok => Color::Green,
+            Level::Warn => Color::Yellow,
+            Level::Critical => Color::Red,
+        }
+    }
+
+    /// Color for the splash canvas, which takes RGB.
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Level::Ok => (80, 200, 120),
+            Level::Warn => (230, 200, 80),
+            Level::Critical => (230, 90, 80),
+        }
+    }
+}
+
+/// A percentage drawn as `filled` of `width` block cells.
+pub struct Gauge {
+    pub filled: usize,
+    pub width: usize,
+    pub level: Level,
+}
+
+impl Gauge {
+    /// Gauge for a field, or `None` if it has no `{percent}` right now.
+    /// Battery colors by how much charge is gone, so a full battery is green.
+    pub fn new(info: &SystemInfo, field: &str, value: &str, display: &FieldDisplay) -> Option<Self> {
+        let percent = field::percent(info, field, value)?.min(100);
+        let width = display.width.map_or(DEFAULT_WIDTH, usize::from).max(1);
+        let load = if field == "Battery" { 100 - percent } else { percent };
+        let level = if load >= display.critical.unwrap_or(CRITICAL) {
+            Level::Critical
+        } else if load >= display.warn.unwrap_or(WARN) {
+            Level::Warn
+        } else {
+            Level::Ok
+        };
+        Some(Gauge {
+            filled: ((percent * width as u64 + 50) / 100) as usize,
+            width,
+            level,
+        })
+    }
+
+    /// Filled and empty cells, e.g. `("██████", "░░░░")`.
+    pub fn cells(&self) -> (String, String) {
+        ("█".repeat(self.filled), "░".repeat(self.width - self.filled))
+    }
+}
@@ -1,8 +1,103 @@
 use std::path::Path;
 use sysinfo::System;
 
-use crate::cache::Cache;
+use crate::cache::{Cache, Policy};
+use crate::config::Config;
+use crate::field;
+
+/// Every value blaeckfetch gathers, keyed as in the cache, with its caching policy.
+pub const SOURCES: &[(&str, Policy)] = &[
+    ("os", Policy::Boot),
+    ("host", Policy::Boot),
+    ("kernel", Policy::Boot),
+    ("cpu", Policy::Boot),
+    ("gpu", Policy::Boot),
+    ("de", Policy::Boot),
+    ("wm", Policy::Boot),
+    ("shell", Policy::Boot),
+    ("resolution", Policy::Boot),
+    ("wm_theme", Policy::Boot),
+    ("packages", Policy::Boot),
+    ("user", Policy::Fresh),
+    ("hostname", Policy::Fresh),
+    ("uptime", Policy::Fresh),
+    ("terminal", Policy::Fresh),
+    ("memory", Policy::Fresh),
+    ("disk", Policy::Fresh),
+    ("battery", Policy::Fresh),
+    ("local_ip", Policy::Fresh),
+];
+
+/// Source key of each field, used as its name in `--format` output.
+pub const FIELD_KEYS: &[(&str, &str)] = &[
+    ("OS", "os"),
+    ("Host", "host"),
+    ("Kernel", "kernel"),
+    ("Uptime", "uptime"),
+    ("Packages", "packages"),
+    ("Shell", "shell"),
+    ("Resolution", "resolution"),
+    ("DE", "de"),
+    ("WM", "wm"),
+    ("WM Theme", "wm_theme"),
+    ("Terminal", "terminal"),
+    ("CPU", "cpu"),
+    ("GPU", "gpu"),
+    ("Memory", "memory"),
+    ("Disk (/)", "disk"),
+    ("Battery", "battery"),
+    ("Local IP", "local_ip"),
+];
+
+/// Field for a source key or field name, ignoring case: `"disk"` or `"Disk (/)"`.
+pub fn find_field(name: &str) -> Option<(&'static str, &'static str)> {
+    FIELD_KEYS
+        .iter()
+        .find(|(f, k)| f.eq_ignore_ascii_case(name) || k.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Caching policy for a source key, or None if the key is unknown.
+pub fn policy(key: &str) -> Option<Policy> {
+    SOURCES.iter().find(|(k, _)| *k == key).map(|(_, p)| *p)
+}
+
+/// Compute a single value by its source key, bypassing the cache.
+pub fn compute(key: &str, sys: &System) -> Option<String> {
+    Some(match key {
+        "os" => get_os_version(),
+        "host" => get_host_model(),
+        "kernel" => get_kernel(),
+        "cpu" => get_cpu(sys),
+        "gpu" => get_gpu(sys),
+        "de" => get_de(),
+        "wm" => get_wm(),
+        "shell" => get_shell(),
+        "resolution" => get_resolution(),
+        "wm_theme" => get_wm_theme(),
+        "packages" => get_packages(),
+        "user" => get_user(),
+        "hostname" => get_hostname(),
+        "uptime" => get_uptime(),
+        "terminal" => get_terminal(),
+        "memory" => get_memory(sys),
+        "disk" => get_disk(),
+        "battery" => get_battery(),
+        "local_ip" => get_local_ip(),
+        _ => return None,
+    })
+}
 
+/// System handle with just the CPU and memory data the providers need.
+pub fn system() -> System {
+    System::new_with_specifics(
+        sysinfo::RefreshKind::new()
+            .with_cpu(sysinfo::CpuRefreshKind::new())
+            .with_memory(sysinfo::MemoryRefreshKind::everything()),
+    )
+}
+
+#[derive(Default)]
 pub struct SystemInfo {
     pub user: String,
     pub hostname: String,
@@ -21,16 +116,19 @@ pub struct SystemInfo {
     pub gpu: String,
     pub memory: String,
     pub disk: String,
+    pub battery: String,
     pub local_ip: String,
+    /// Raw values behind some display strings, exposed to `[format]` templates.
+    pub uptime_secs: u64,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    /// Root filesystem (total, available) in bytes, if statfs succeeded.
+    pub disk_space: Option<(u64, u64)>,
 }
 
 impl SystemInfo {
     pub fn gather() -> Self {
-        let sys = System::new_with_specifics(
-            sysinfo::RefreshKind::new()
-                .with_cpu(sysinfo::CpuRefreshKind::new())
-                .with_memory(sysinfo::MemoryRefreshKind::everything()),
-        );
+        let sys = system();
 
         let mut cache = Cache::load();
 
@@ -50,13 +148,15 @@ impl SystemInfo {
         cache.save();
 
         // Always fresh (changes every run)
+        let uptime_secs = System::uptime();
+        let disk_space = disk_space();
         Self {
             user: get_user(),
             hostname: get_hostname(),
             os,
             host,
             kernel,
-            uptime: get_uptime(),
+            uptime: format_uptime(uptime_secs),
             packages,
             shell,
             resolution,
@@ -67,19 +167,89 @@ impl SystemInfo {
             cpu,
             gpu,
             memory: get_memory(&sys),
-            disk: get_disk(),
+            disk: format_disk(disk_space),
+            battery: get_battery(),
             local_ip: get_local_ip(),
+            uptime_secs,
+            memory_used: sys.used_memory(),
+            memory_total: sys.total_memory(),
+            disk_space,
         }
     }
 
+    /// Gather a single source key through the same providers and cache as
+    /// `gather`, leaving every other field empty. None for an unknown key.
+    pub fn gather_one(key: &str) -> Option<Self> {
+        let mut info = Self::default();
+        match policy(key)? {
+            Policy::Boot => {
+                let sys = if matches!(key, "cpu" | "gpu") { system() } else { System::new() };
+                let mut cache = Cache::load();
+                let value = cache.get_or_insert(key, || compute(key, &sys).unwrap_or_default());
+                cache.save();
+                info.set(key, value);
+            }
+            // Fields with raw data behind their placeholders fill that in too
+            Policy::Fresh => match key {
+                "uptime" => {
+                    info.uptime_secs = System::uptime();
+                    info.uptime = format_uptime(info.uptime_secs);
+                }
+                "memory" => {
+                    let sys = system();
+                    info.memory = get_memory(&sys);
+                    info.memory_used = sys.used_memory();
+                    info.memory_total = sys.total_memory();
+                }
+                "disk" => {
+                    info.disk_space = disk_space();
+                    info.disk = format_disk(info.disk_space);
+                }
+                _ => info.set(key, compute(key, &System::new())?),
+            },
+        }
+        Some(info)
+    }
+
+    fn set(&mut self, key: &str, value: String) {
+        let slot = match key {
+            "os" => &mut self.os,
+            "host" => &mut self.host,
+            "kernel" => &mut self.kernel,
+            "cpu" => &mut self.cpu,
+            "gpu" => &mut self.gpu,
+            "de" => &mut self.de,
+            "wm" => &mut self.wm,
+            "shell" => &mut self.shell,
+            "resolution" => &mut self.resolution,
+            "wm_theme" => &mut self.wm_theme,
+            "packages" => &mut self.packages,
+            "user" => &mut self.user,
+            "hostname" => &mut self.hostname,
+            "uptime" => &mut self.uptime,
+            "terminal" => &mut self.terminal,
+            "memory" => &mut self.memory,
+            "disk" => &mut self.disk,
+            "battery" => &mut self.battery,
+            "local_ip" => &mut self.local_ip,
+            _ => return,
+        };
+        *slot = value;
+    }
+
     pub fn title(&self) -> String {
         format!("{}@{}", self.user, self.hostname)
     }
 
-    pub fn to_json(&self) -> String {
+    /// Fields as a JSON object. With a config, values use their `[format]` templates.
+    pub fn to_json(&self, cfg: Option<&Config>) -> String {
         let mut map = serde_json::Map::new();
         for (k, v) in self.fields() {
-            map.insert(k.to_string(), serde_json::Value::String(v.to_string()));
+            let value = match cfg.and_then(|c| c.format_for(k)) {
+                Some(template) => field::apply(self, k, v, template),
+                None => v.to_string(),
+            };
+            map.insert(k.to_string(), serde_json::Value::String(value));
         }
         serde_json::to_string_pretty(&map).unwrap_or_else(|_| "{}".into())
     }
@@ -101,6 +271,7 @@ impl SystemInfo {
             ("GPU", &self.gpu),
             ("Memory", &self.memory),
             ("Disk (/)", &self.disk),
+            ("Battery", &self.battery),
             ("Local IP", &self.local_ip),
         ]
     }
@@ -180,7 +351,10 @@ fn get_kernel() -> String {
 }
 
 fn get_uptime() -> String {
-    let secs = System::uptime();
+    format_uptime(System::uptime())
+}
+
+fn format_uptime(secs: u64) -> String {
     let days = secs / 86400;
     let hours = (secs % 86400) / 3600;
     let mins = (secs % 3600) / 60;
@@ -524,6 +698,74 @@ fn get_memory(sys: &System) -> String {
 }
 
 fn get_disk() -> String {
+    format_disk(disk_space())
+}
+
+fn format_disk(space: Option<(u64, u64)>) -> String {
+    match space {
+        Some((total, available)) => {
+            let total = total / 1024 / 1024 / 1024;
+            let available = available / 1024 / 1024 / 1024;
+            let used = total - available;
+            let percent = (used * 100).checked_div(total).unwrap_or(0);
+            format!("{}GiB / {}GiB ({}%)", used, total, percent)
+        }
+        None => "Unknown".into(),
+    }
+}
+
+/// Charge of the first battery, e.g. "85% (Charging)", or "Unknown" if there is none.
+fn get_battery() -> String {
+    #[cfg(target_os = "macos")]
+    {
+        // " -InternalBattery-0 (id=1234)\t85%; charging; 1:02 remaining present: true"
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()
+            .and_then(|out| {
+                let text = String::from_utf8_lossy(&out.stdout).to_string();
+                let line = text.lines().find(|l| l.contains("InternalBattery"))?;
+                let mut parts = line.split('\t').nth(1)?.split(';').map(str::trim);
+                let percent = parts.next()?.to_string();
+                let status = parts.next().unwrap_or("");
+                let mut chars = status.chars();
+                Some(match chars.next() {
+                    None => percent,
+                    Some(first) => format!("{} ({}{})", percent, first.to_uppercase(), chars.as_str()),
+                })
+            })
+            .unwrap_or_else(|| "Unknown".into())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+            return "Unknown".into();
+        };
+        let mut batteries: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                std::fs::read_to_string(p.join("type")).is_ok_and(|t| t.trim() == "Battery")
+            })
+            .collect();
+        batteries.sort();
+        batteries
+            .first()
+            .and_then(|p| {
+                let capacity = std::fs::read_to_string(p.join("capacity")).ok()?;
+                let status = std::fs::read_to_string(p.join("status")).unwrap_or_default();
+                Some(match status.trim() {
+                    "" | "Unknown" => format!("{}%", capacity.trim()),
+                    s => format!("{}% ({})", capacity.trim(), s),
+                })
+            })
+            .unwrap_or_else(|| "Unknown".into())
+    }
+}
+
+/// Root filesystem (total, available) in bytes.
+fn disk_space() -> Option<(u64, u64)> {
     #[cfg(unix)]
     {
         use std::mem::MaybeUninit;
@@ -578,18 +820,14 @@ fn get_disk() -> String {
             if statfs(path.as_ptr() as *const i8, buf.as_mut_ptr()) == 0 {
                 let buf = buf.assume_init();
                 let block_size = buf.f_bsize as u64;
-                let total = (buf.f_blocks * block_size) / 1024 / 1024 / 1024;
-                let available = (buf.f_bavail * block_size) / 1024 / 1024 / 1024;
-                let used = total - available;
-                let percent = if total > 0 { (used * 100) / total } else { 0 };
-                return format!("{}GiB / {}GiB ({}%)", used, total, percent);
+                return Some((buf.f_blocks * block_size, buf.f_bavail * block_size));
             }
         }
-        "Unknown".into()
+        None
     }
     #[cfg(not(unix))]
     {
-        "Unknown".into()
+        None
     }
 }
 
@@ -3,14 +3,20 @@ mod boot;
 mod cache;
 mod color;
 mod config;
+mod export;
+mod field;
+mod gauge;
 mod info;
 mod logo;
 mod mode;
+mod output;
 mod render;
+mod text;
+mod xdg;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use color::Theme;
-use config::Config;
+use config::{Config, Override};
 use info::SystemInfo;
 use mode::Mode;
 
@@ -34,10 +40,51 @@ struct Args {
     #[arg(long)]
     no_logo: bool,
 
+    /// When to use colors: auto (terminal and NO_COLOR unset), always, never
+    #[arg(long, value_name = "WHEN", value_parser = ["auto", "always", "never"])]
+    color_mode: Option<String>,
+
+    /// Print `Label: value` lines with no logo, title or colors
+    #[arg(long)]
+    plain: bool,
+
     /// Output as JSON
     #[arg(long)]
     json: bool,
 
+    /// Output as JSON with values rendered through their [format] templates
+    #[arg(long)]
+    json_formatted: bool,
+
+    /// Print one value and nothing else, e.g. --get kernel (any field or cache key)
+    #[arg(long, value_name = "FIELD")]
+    get: Option<String>,
+
+    /// With --get, print the value as detected, ignoring [format] templates
+    #[arg(long, requires = "get")]
+    raw: bool,
+
+    /// Write the rendered output to an .svg or .html file instead of the terminal
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["plain", "format", "json", "json_formatted"])]
+    export: Option<std::path::PathBuf>,
+
+    /// Print the fields in a machine-readable format with version, timestamp
+    /// and hostname (see the Output Formats docs for the schema)
+    #[arg(long, value_name = "FORMAT", value_parser = output::FORMATS.to_vec())]
+    format: Option<String>,
+
+    /// With --format, put JSON and YAML on a single line
+    #[arg(long, requires = "format")]
+    compact: bool,
+
+    /// With --format, include only these fields, e.g. --fields os,kernel,memory
+    #[arg(long, value_name = "FIELDS", value_delimiter = ',', requires = "format")]
+    fields: Vec<String>,
+
+    /// List every field and the placeholders its [format] template can use
+    #[arg(long)]
+    list_fields: bool,
+
     /// Clear the cache and re-gather all info
     #[arg(long)]
     clear_cache: bool,
@@ -46,9 +93,32 @@ struct Args {
     #[arg(long)]
     config: Option<String>,
 
-    /// Print default config to stdout
+    /// Print a config to stdout: the commented default template, or the
+    /// effective config after merging file, profile, env and CLI overrides
+    #[arg(
+        long,
+        value_name = "WHICH",
+        num_args = 0..=1,
+        default_missing_value = "default",
+        value_parser = ["default", "effective"]
+    )]
+    print_config: Option<String>,
+
+    /// Rewrite deprecated keys in the config file (e.g. [boot] to [splash])
+    #[arg(long)]
+    migrate_config: bool,
+
+    /// Use a named [profile.<name>] table from the config
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Override any config key, e.g. --set splash.timeout=10 --set fields=OS,CPU
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Validate the config file and report every problem, then exit
     #[arg(long)]
-    print_config: bool,
+    check_config: bool,
 
     /// Animate the logo (background color cycling)
     #[arg(long)]
@@ -81,34 +151,104 @@ struct Args {
     /// Splash screen alignment: right
     #[arg(long)]
     right: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect or refresh the boot-cycle cache
+    Cache {
+        #[command(subcommand)]
+        action: Option<CacheAction>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// List every value with its cache policy, age and cached value (default)
+    List,
+    /// Recompute a single cached value, e.g. `cache refresh packages`
+    Refresh { key: String },
+    /// Print the cache file location
+    Path,
+    /// Delete the cache
+    Clear,
 }
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
 
-    if args.print_config {
+    if args.print_config.as_deref() == Some("default") {
         print!("{}", config::generate_default());
         return Ok(());
     }
 
+    if args.list_fields {
+        list_fields();
+        return Ok(());
+    }
+
+    if let Some(Command::Cache { action }) = args.command {
+        return run_cache(action.unwrap_or(CacheAction::List));
+    }
+
+    if args.migrate_config {
+        return migrate_config(args.config.as_deref());
+    }
+
+    let cli = cli_overrides(&args);
+
+    if args.check_config {
+        return check_config(args.config.as_deref(), args.profile.as_deref(), &cli);
+    }
+
     if args.clear_cache {
         cache::clear();
     }
 
-    let cfg = Config::load(args.config.as_deref());
+    if let Some(name) = &args.get {
+        return get_field(name, args.raw, &args, &cli);
+    }
+
+    // Checked before gathering, so a bad file name fails fast
+    let export = match args.export.as_deref().map(export::Format::from_path).transpose() {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let cfg = Config::load(args.config.as_deref(), args.profile.as_deref(), &cli);
+
+    if args.print_config.is_some() {
+        print!("{}", cfg.to_toml());
+        return Ok(());
+    }
 
     let info = SystemInfo::gather();
 
-    if args.json {
-        println!("{}", info.to_json());
+    if let Some(format) = &args.format {
+        match output::Report::new(&info, &args.fields) {
+            Ok(report) => println!("{}", report.render(format, args.compact)),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+
+    if args.json || args.json_formatted {
+        let format = if args.json_formatted { Some(&cfg) } else { None };
+        println!("{}", info.to_json(format));
         return Ok(());
     }
 
-    // CLI flags override config values
-    let color_name = args
-        .color
-        .or(cfg.color.clone())
-        .unwrap_or_else(|| "green".into());
+    // CLI flags were merged into the config as overrides
+    let color_name = cfg.color.clone().unwrap_or_else(|| "green".into());
 
     let mut theme = Theme::by_name(&color_name);
 
@@ -126,26 +266,23 @@ fn main() -> std::io::Result<()> {
         if let Some(c) = &colors.logo {
             theme.logo = c.to_color();
         }
+        if let Some(c) = &colors.value {
+            theme.value = Some(c.to_color());
+        }
     }
 
-    // Resolve mode: shorthand flags > --mode > config > Default
-    let mode = if args.neofetch {
-        Mode::Neofetch
-    } else if args.splash || args.boot {
-        Mode::Splash
-    } else if let Some(ref mode_str) = args.mode {
-        Mode::from_str(mode_str).unwrap_or_else(|| {
-            eprintln!("warning: unknown mode '{}', using 'default'", mode_str);
-            Mode::Default
-        })
-    } else if let Some(ref mode_str) = cfg.mode {
-        Mode::from_str(mode_str).unwrap_or(Mode::Default)
-    } else {
-        Mode::Default
-    };
+    let mode = cfg
+        .mode
+        .as_deref()
+        .and_then(Mode::from_str)
+        .unwrap_or(Mode::Default);
+
+    if args.plain {
+        return render::render_plain(&info, &theme, &cfg, mode);
+    }
 
     // Resolve logo (mode-aware)
-    let logo_file = args.logo_file.or(cfg.logo_file.clone());
+    let logo_file = cfg.logo_file.clone();
 
     let logo_art = if args.no_logo {
         String::new()
@@ -155,8 +292,7 @@ fn main() -> std::io::Result<()> {
             logo::detect().art.to_string()
         })
     } else {
-        let logo_name = args.logo.or(cfg.logo.clone());
-        match logo_name.as_deref() {
+        match cfg.logo.as_deref() {
             Some("none" | "off") => String::new(),
             Some("auto") => logo::detect().art.to_string(),
             Some(name) => logo::by_name(name).art.to_string(),
@@ -168,19 +304,21 @@ fn main() -> std::io::Result<()> {
         }
     };
 
+    if let (Some(path), Some(format)) = (&args.export, export) {
+        // Splash is an animation; export the default layout instead
+        let mode = if mode == Mode::Splash { Mode::Default } else { mode };
+        let ansi = render::capture(&info, &logo_art, &theme, &cfg, mode)?;
+        if let Err(e) = std::fs::write(path, export::document(&ansi, format, &cfg)) {
+            eprintln!("error: cannot write {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     if mode == Mode::Splash {
         let boot_cfg = cfg.splash_config();
 
-        // Resolve alignment: CLI flags win, then config, then default "left"
-        let align = if args.center {
-            "center"
-        } else if args.left {
-            "left"
-        } else if args.right {
-            "right"
-        } else {
-            boot_cfg.and_then(|b| b.align.as_deref()).unwrap_or("left")
-        };
+        let align = boot_cfg.and_then(|b| b.align.as_deref()).unwrap_or("left");
         let centered = align == "center";
         let right_aligned = align == "right";
 
@@ -271,7 +409,7 @@ fn main() -> std::io::Result<()> {
         let vhs_mode = render_mode == boot::image_proto::RenderMode::Inline;
         let entrance = boot_cfg.and_then(|b| b.entrance.as_deref()).unwrap_or("slow");
         let exit = boot_cfg.and_then(|b| b.exit.as_deref()).unwrap_or("slow");
-        boot::run(&info, centered, right_aligned, bg, raw_image, render_mode, boot_w, boot_h, timeout, image_source, image_cell_size, (min_w, min_h), (max_w, max_h), entrance, exit, vhs_mode);
+        boot::run(&info, &cfg, centered, right_aligned, bg, raw_image, render_mode, boot_w, boot_h, timeout, image_source, image_cell_size, (min_w, min_h), (max_w, max_h), entrance, exit, vhs_mode);
         return Ok(());
     }
 
@@ -281,3 +419,205 @@ fn main() -> std::io::Result<()> {
         render::render(&info, &logo_art, &theme, &cfg, mode)
     }
 }
+
+/// `--set` arguments followed by dedicated flags, which win over them.
+fn cli_overrides(args: &Args) -> Vec<Override> {
+    let mut cli: Vec<Override> = args.set.iter().map(|s| Override::set(s)).collect();
+
+    if let Some(c) = &args.color {
+        cli.push(Override::flag("--color", "color", c));
+    }
+    if let Some(l) = &args.logo {
+        cli.push(Override::flag("--logo", "logo", l));
+    }
+    if let Some(f) = &args.logo_file {
+        cli.push(Override::flag("--logo-file", "logo_file", f));
+    }
+    if args.no_logo {
+        cli.push(Override::flag("--no-logo", "logo", "none"));
+    }
+    if let Some(m) = &args.color_mode {
+        cli.push(Override::flag("--color-mode", "color_mode", m));
+    }
+
+    // Mode: shorthand flags > --mode
+    if args.neofetch {
+        cli.push(Override::flag("--neofetch", "mode", "neofetch"));
+    } else if args.splash || args.boot {
+        cli.push(Override::flag("--splash", "mode", "splash"));
+    } else if let Some(m) = &args.mode {
+        cli.push(Override::flag("--mode", "mode", m));
+    }
+
+    // Splash alignment: --center > --left > --right
+    if args.center {
+        cli.push(Override::flag("--center", "splash.align", "center"));
+    } else if args.left {
+        cli.push(Override::flag("--left", "splash.align", "left"));
+    } else if args.right {
+        cli.push(Override::flag("--right", "splash.align", "right"));
+    }
+
+    cli
+}
+
+fn migrate_config(path: Option<&str>) -> std::io::Result<()> {
+    let path = path
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(config::default_config_path);
+    match config::migrate(&path) {
+        Ok(changes) if changes.is_empty() => println!("{}: nothing to migrate", path.display()),
+        Ok(changes) => {
+            for c in &changes {
+                println!("{}: {}", path.display(), c);
+            }
+            println!("original saved as {}", path.with_extension("toml.bak").display());
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+fn check_config(path: Option<&str>, profile: Option<&str>, cli: &[Override]) -> std::io::Result<()> {
+    let (_, mut diags) = Config::load_checked(path, profile, cli);
+    let shown = path
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(config::default_config_path);
+    // There is nothing to validate without a file; load_checked already
+    // reports a missing --config path
+    if path.is_none() && !shown.exists() {
+        diags.insert(
+            0,
+            config::check::Diagnostic::plain("--check-config", format!("cannot read {}: not found", shown.display())),
+        );
+    }
+    if diags.is_empty() {
+        println!("{}: ok", shown.display());
+        return Ok(());
+    }
+    for d in &diags {
+        eprintln!("error: {}", d);
+    }
+    eprintln!(
+        "{} problem{} found",
+        diags.len(),
+        if diags.len() == 1 { "" } else { "s" }
+    );
+    std::process::exit(1);
+}
+
+/// `--get`: gather and print a single value. Only the config needed for its
+/// `[format]` template is loaded, and none with `--raw`.
+fn get_field(name: &str, raw: bool, args: &Args, cli: &[Override]) -> std::io::Result<()> {
+    let (field, key) = match info::find_field(name) {
+        Some((field, key)) => (Some(field), key),
+        None => match info::SOURCES.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)) {
+            Some((key, _)) => (None, *key),
+            None => {
+                let keys = info::SOURCES.iter().map(|(k, _)| *k);
+                match config::check::suggest(name, keys) {
+                    Some(s) => eprintln!("error: unknown field '{}' (did you mean '{}'?)", name, s),
+                    None => eprintln!("error: unknown field '{}'", name),
+                }
+                std::process::exit(2);
+            }
+        },
+    };
+
+    let Some(info) = SystemInfo::gather_one(key) else {
+        std::process::exit(1);
+    };
+    let value = match field {
+        Some(field) => info.fields().into_iter().find(|(f, _)| *f == field).map(|(_, v)| v).unwrap_or_default(),
+        // The only source keys that are not fields
+        None if key == "user" => info.user.as_str(),
+        None => info.hostname.as_str(),
+    };
+
+    let formatted = field.filter(|_| !raw).and_then(|field| {
+        let cfg = Config::load(args.config.as_deref(), args.profile.as_deref(), cli);
+        cfg.format_for(field).map(|t| field::apply(&info, field, value, t))
+    });
+    println!("{}", formatted.as_deref().unwrap_or(value));
+    Ok(())
+}
+
+/// Print each field with the placeholders its `[format]` template accepts.
+fn list_fields() {
+    for (name, placeholders) in field::PLACEHOLDERS {
+        let width = placeholders.iter().map(|(p, _)| p.len()).max().unwrap_or(0).max(5) + 2;
+        println!("{}", name);
+        println!("  {:<width$}  the value as normally shown", "{value}", width = width);
+        for (p, desc) in placeholders.iter() {
+            println!("  {:<width$}  {}", format!("{{{}}}", p), desc, width = width);
+        }
+    }
+}
+
+fn run_cache(action: CacheAction) -> std::io::Result<()> {
+    match action {
+        CacheAction::List => {
+            let cache = cache::Cache::load();
+            let width = info::SOURCES.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+            println!("{:<width$}  {:<6}  {:<8}  VALUE", "KEY", "POLICY", "AGE", width = width);
+            for (key, policy) in info::SOURCES {
+                let (age, value) = match (policy, cache.get(key)) {
+                    (cache::Policy::Boot, Some(entry)) => (format_age(entry.age()), entry.value.clone()),
+                    (cache::Policy::Boot, None) => ("-".into(), "(not cached)".into()),
+                    (cache::Policy::Fresh, _) => ("-".into(), "(computed every run)".into()),
+                };
+                println!("{:<width$}  {:<6}  {:<8}  {}", key, policy.name(), age, value, width = width);
+            }
+        }
+        CacheAction::Refresh { key } => match info::policy(&key) {
+            Some(cache::Policy::Boot) => {
+                let mut cache = cache::Cache::load();
+                let sys = info::system();
+                let value = cache.refresh(&key, || info::compute(&key, &sys).unwrap_or_default());
+                cache.save();
+                println!("{}", value);
+            }
+            Some(cache::Policy::Fresh) => {
+                eprintln!("error: '{}' is not cached (computed every run)", key);
+                std::process::exit(1);
+            }
+            None => {
+                let keys: Vec<&str> = info::SOURCES
+                    .iter()
+                    .filter(|(_, p)| *p == cache::Policy::Boot)
+                    .map(|(k, _)| *k)
+                    .collect();
+                eprintln!("error: unknown cache key '{}' (expected one of: {})", key, keys.join(", "));
+                std::process::exit(1);
+            }
+        },
+        CacheAction::Path => match cache::Cache::load().path() {
+            Some(path) => println!("{}", path.display()),
+            None => {
+                eprintln!("error: no usable cache directory");
+                std::process::exit(1);
+            }
+        },
+        CacheAction::Clear => cache::clear(),
+    }
+    Ok(())
+}
+
+/// Compact age like "3d 4h", "12m" or "40s".
+fn format_age(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = (secs % 86400) / 3600;
+    let mins = (secs % 3600) / 60;
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}s", secs)
+    }
+}
@@ -27,7 +27,11 @@ impl Mode {
                 "CPU".into(),
                 "Memory".into(),
             ],
-            Mode::Neofetch => Config::default_fields(),
+            // Everything except Battery, which most desktops do not have
+            Mode::Neofetch => Config::default_fields()
+                .into_iter()
+                .filter(|f| f != "Battery")
+                .collect(),
             Mode::Splash => vec![],
         }
     }
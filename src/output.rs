@@ -0,0 +1,224 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::info::{self, SystemInfo};
+
+/// Bumped whenever a key is renamed or removed; new keys do not change it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Names accepted by `--format`.
+pub const FORMATS: &[&str] = &["json", "yaml", "toml", "env", "csv"];
+
+/// Metadata keys whose values are written as numbers, not strings, where the
+/// format has numbers.
+const NUMERIC: &[&str] = &["schema_version"];
+
+/// Prefix for `--format env` variables. Not `BLAECKFETCH_`, which would be
+/// read back as config overrides.
+const ENV_PREFIX: &str = "FETCH_";
+
+/// A snapshot in the documented `--format` schema: metadata, then the
+/// requested fields under their source keys, in display order.
+pub struct Report {
+    version: &'static str,
+    timestamp: String,
+    hostname: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Report {
+    /// Every field, or only those named in `only` (source keys or field names).
+    pub fn new(info: &SystemInfo, only: &[String]) -> Result<Self, String> {
+        let mut wanted = Vec::new();
+        for name in only {
+            match info::find_field(name) {
+                Some((_, key)) => wanted.push(key),
+                None => {
+                    let keys = info::FIELD_KEYS.iter().map(|(_, k)| *k);
+                    return Err(match crate::config::check::suggest(name, keys) {
+                        Some(s) => format!("unknown field '{}' (did you mean '{}'?)", name, s),
+                        None => format!("unknown field '{}'", name),
+                    });
+                }
+            }
+        }
+
+        // Matched by name, so neither list depends on the other's order
+        let fields = info
+            .fields()
+            .into_iter()
+            .filter_map(|(name, value)| Some((info::find_field(name)?.1, value)))
+            .filter(|(key, _)| wanted.is_empty() || wanted.contains(key))
+            .map(|(key, value)| (key, value.to_string()))
+            .collect();
+
+        Ok(Report {
+            version: env!("CARGO_PKG_VERSION"),
+            timestamp: utc_timestamp(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+            ),
+            hostname: info.hostname.clone(),
+            fields,
+        })
+    }
+
+    /// Metadata keys and values, in schema order.
+    fn metadata(&self) -> [(&'static str, String); 4] {
+        [
+            ("schema_version", SCHEMA_VERSION.to_string()),
+            ("version", self.version.to_string()),
+            ("timestamp", self.timestamp.clone()),
+            ("hostname", self.hostname.clone()),
+        ]
+    }
+
+    /// Render in one of `FORMATS`. `compact` puts JSON and YAML on one line.
+    pub fn render(&self, format: &str, compact: bool) -> String {
+        match format {
+            "yaml" => self.yaml(compact),
+            "toml" => self.toml(),
+            "env" => self.env(),
+            "csv" => self.csv(),
+            _ => self.json(compact),
+        }
+    }
+
+    fn json(&self, compact: bool) -> String {
+        let out = if compact {
+            serde_json::to_string(self)
+        } else {
+            serde_json::to_string_pretty(self)
+        };
+        out.unwrap_or_else(|_| "{}".into())
+    }
+
+    /// YAML with every string double-quoted; JSON string escapes are valid YAML.
+    fn yaml(&self, compact: bool) -> String {
+        let quote = |s: &str| serde_json::Value::from(s).to_string();
+        let fields: Vec<(&str, String)> = self.fields.iter().map(|(k, v)| (*k, quote(v))).collect();
+        let meta: Vec<(&str, String)> = self
+            .metadata()
+            .into_iter()
+            .map(|(k, v)| if NUMERIC.contains(&k) { (k, v) } else { (k, quote(&v)) })
+            .collect();
+
+        if compact {
+            let pairs = |items: &[(&str, String)]| -> String {
+                items.iter().map(|(k, v)| format!("{}: {}", k, v)).collect::<Vec<_>>().join(", ")
+            };
+            return format!("{{{}, fields: {{{}}}}}", pairs(&meta), pairs(&fields));
+        }
+
+        let mut out = String::new();
+        for (k, v) in &meta {
+            out.push_str(&format!("{}: {}\n", k, v));
+        }
+        out.push_str("fields:\n");
+        for (k, v) in &fields {
+            out.push_str(&format!("  {}: {}\n", k, v));
+        }
+        out.trim_end().to_string()
+    }
+
+    fn toml(&self) -> String {
+        let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+        let mut out = String::new();
+        for (k, v) in self.metadata() {
+            let v = if NUMERIC.contains(&k) { v } else { quote(&v) };
+            out.push_str(&format!("{} = {}\n", k, v));
+        }
+        out.push_str("\n[fields]\n");
+        for (k, v) in &self.fields {
+            out.push_str(&format!("{} = {}\n", k, quote(v)));
+        }
+        out.trim_end().to_string()
+    }
+
+    /// `FETCH_KEY='value'` lines, safe to `eval` or `source` in a POSIX shell.
+    fn env(&self) -> String {
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+        self.metadata()
+            .iter()
+            .map(|(k, v)| (*k, v.as_str()))
+            .chain(self.fields.iter().map(|(k, v)| (*k, v.as_str())))
+            .map(|(k, v)| format!("{}{}={}", ENV_PREFIX, k.to_uppercase(), quote(v)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// A header row and one record, quoted per RFC 4180 where needed.
+    fn csv(&self) -> String {
+        let quote = |s: &str| {
+            if s.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        let meta = self.metadata();
+        let (keys, values): (Vec<&str>, Vec<String>) = meta
+            .iter()
+            .map(|(k, v)| (*k, quote(v)))
+            .chain(self.fields.iter().map(|(k, v)| (*k, quote(v))))
+            .unzip();
+        format!("{}\n{}", keys.join(","), values.join(","))
+    }
+}
+
+/// Serialized by hand so keys keep schema order rather than sorting.
+impl Serialize for Report {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Fields<'a>(&'a [(&'static str, String)]);
+        impl Serialize for Fields<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.len()))?;
+                for (k, v) in self.0 {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+        }
+
+        let meta = self.metadata();
+        let mut map = serializer.serialize_map(Some(meta.len() + 1))?;
+        for (k, v) in &meta {
+            if NUMERIC.contains(k) {
+                map.serialize_entry(k, &v.parse::<u64>().unwrap_or_default())?;
+            } else {
+                map.serialize_entry(k, v)?;
+            }
+        }
+        map.serialize_entry("fields", &Fields(&self.fields))?;
+        map.end()
+    }
+}
+
+/// RFC 3339 UTC time such as `2025-03-01T14:05:09Z` for seconds since the epoch.
+fn utc_timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
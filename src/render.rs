@@ -1,13 +1,16 @@
 use blaeck::prelude::*;
 use blaeck::Blaeck;
-use std::io;
+use std::io::{self, Write};
 
 use crate::animate;
 use crate::color::{self, Theme};
-use crate::config::Config;
+use crate::config::{self, Config, TextStyle};
+use crate::field;
+use crate::gauge::Gauge;
 use crate::info::SystemInfo;
 use crate::logo;
 use crate::mode::Mode;
+use crate::text;
 
 /// Build the moon as a blaeck Column of Rows with per-cell RGB colors.
 fn moon_element() -> Element {
@@ -56,200 +59,1055 @@ fn moon_element() -> Element {
     )
 }
 
-pub fn render(info: &SystemInfo, logo: &str, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<()> {
-    let title = info.title();
-    let title_len = title.len();
+/// Resolved color and attributes of a label or value.
+#[derive(Clone, Copy, Default)]
+struct Style {
+    color: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    dim: bool,
+}
+
+impl Style {
+    /// Layer the keys set in a `[style]` table over this style.
+    fn with(mut self, over: Option<&TextStyle>) -> Self {
+        if let Some(o) = over {
+            if let Some(c) = &o.color {
+                self.color = Some(c.to_color());
+            }
+            self.bold = o.bold.unwrap_or(self.bold);
+            self.italic = o.italic.unwrap_or(self.italic);
+            self.underline = o.underline.unwrap_or(self.underline);
+            self.dim = o.dim.unwrap_or(self.dim);
+        }
+        self
+    }
+
+    fn text(&self, content: String) -> Element {
+        Element::node::<Text>(
+            TextProps {
+                content,
+                color: self.color,
+                bold: self.bold,
+                italic: self.italic,
+                underline: self.underline,
+                dim: self.dim,
+                ..Default::default()
+            },
+            vec![],
+        )
+    }
+}
+
+/// A field as it will be shown.
+struct FieldRow {
+    label: String,
+    value: String,
+    label_style: Style,
+    value_style: Style,
+    /// Drawn before the value when `[format]` asks for a bar
+    bar: Option<Gauge>,
+}
+
+impl FieldRow {
+    fn value_width(&self) -> usize {
+        let bar = match &self.bar {
+            Some(g) if self.value.is_empty() => g.width,
+            Some(g) => g.width + 1,
+            None => 0,
+        };
+        bar + text::width(&self.value)
+    }
+}
+
+/// One line of the info column.
+enum Row {
+    /// `user@host` and its divider
+    Title,
+    /// `"# Hardware"` entry
+    Header(String),
+    /// `"---"` entry
+    Spacer,
+    Field(FieldRow),
+}
+
+/// Info column lines in `fields` order, formatted, with `[when]` rules and
+/// `hide_unknown` applied. The title goes first unless placed or disabled.
+fn rows(info: &SystemInfo, theme: &Theme, cfg: &Config, mode: Mode) -> Vec<Row> {
     let all_fields = info.fields();
+    let layout = cfg.layout_for_mode(mode);
+    let hide_unknown = layout.hide_unknown.unwrap_or(false);
+    let style = cfg.style.clone().unwrap_or_default();
+    let label_style = Style {
+        color: Some(theme.label),
+        bold: true,
+        ..Default::default()
+    }
+    .with(style.label.as_ref());
+    let value_style = Style {
+        color: theme.value,
+        ..Default::default()
+    }
+    .with(style.value.as_ref());
+
     let active = cfg.active_fields_for_mode(mode);
+    let mut rows = Vec::new();
+    if !active.iter().any(|f| f == "Title") && layout.show_title.unwrap_or(true) {
+        rows.push(Row::Title);
+    }
 
-    let mut info_elements: Vec<Element> = Vec::new();
+    for key in active {
+        match key.as_str() {
+            "Title" => {
+                rows.push(Row::Title);
+                continue;
+            }
+            "---" => {
+                rows.push(Row::Spacer);
+                continue;
+            }
+            k if k.starts_with('#') => {
+                rows.push(Row::Header(k.trim_start_matches('#').trim().to_string()));
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some((_, raw)) = all_fields.iter().find(|(k, _)| *k == key.as_str()) else { continue };
+        if hide_unknown && config::is_unknown(raw) {
+            continue;
+        }
 
-    // Title
-    info_elements.push(element! {
-        Text(content: title, color: theme.title, bold: true)
-    });
+        let field_style = style.field.as_ref().and_then(|f| f.get(&key));
+        let mut value_style = value_style.with(field_style.and_then(|f| f.value.as_ref()));
+        let mut hide = false;
+        if let Some(rules) = cfg.when.as_ref().and_then(|w| w.get(&key)) {
+            let number = field::number(info, &key, raw);
+            for rule in rules.iter().filter(|r| r.matches(raw, number)) {
+                hide |= rule.hide.unwrap_or(false);
+                if let Some(c) = &rule.color {
+                    value_style.color = Some(c.to_color());
+                }
+            }
+        }
+        if hide {
+            continue;
+        }
 
-    // Separator — use blaeck Divider
-    info_elements.push(Element::node::<Divider>(
+        // A bar with nothing to measure falls back to the plain value
+        let bar = cfg.bar_for(&key).map(|d| Gauge::new(info, &key, raw, d));
+        let value = match cfg.format_for(&key) {
+            Some(template) if !matches!(bar, Some(None)) => field::apply(info, &key, raw, template),
+            _ => raw.to_string(),
+        };
+        rows.push(Row::Field(FieldRow {
+            label: layout.label_for(&key),
+            value,
+            label_style: label_style.with(field_style.and_then(|f| f.label.as_ref())),
+            value_style,
+            bar: bar.flatten(),
+        }));
+    }
+    rows
+}
+
+/// The line under the title, as wide as the title on screen. A `separator`
+/// character replaces the style's own; `divider = "none"` drops the line.
+fn divider(cfg: &Config, width: usize, theme: &Theme) -> Option<Element> {
+    let style = match cfg.divider.as_deref() {
+        Some("none") => return None,
+        Some("solid") => DividerStyle::Single,
+        Some("double") => DividerStyle::Double,
+        Some("dotted") => DividerStyle::Dotted,
+        Some("bold") => DividerStyle::Bold,
+        Some("ascii") => DividerStyle::Ascii,
+        _ => DividerStyle::Dashed,
+    };
+
+    if let Some(sep) = cfg.separator.as_deref().filter(|s| !s.is_empty()) {
+        let line = sep.repeat(width / text::width(sep).max(1));
+        return Some(element! { Text(content: line, color: theme.separator) });
+    }
+
+    Some(Element::node::<Divider>(
         DividerProps::new()
-            .width(title_len)
-            .line_style(DividerStyle::Dashed)
+            .width(width)
+            .line_style(style)
             .color(theme.separator),
         vec![],
-    ));
+    ))
+}
 
-    // Info fields — filtered and ordered by config
-    let right_align_labels = cfg.label_align.as_deref() == Some("right");
-    let label_on_right = cfg.label_position.as_deref() == Some("right");
-    let right_align_values = cfg.value_align.as_deref() == Some("right");
-    let field_sep = cfg.field_separator.as_deref().unwrap_or(": ");
-    let use_fill = field_sep == "fill";
-
-    // Collect active field data
-    let mut field_data: Vec<(String, String)> = Vec::new();
-    for key in &active {
-        if let Some((_, value)) = all_fields.iter().find(|(k, _)| *k == key.as_str()) {
-            field_data.push((cfg.label_for(key), value.to_string()));
+/// Turns rows into lines, aligning labels and values within one group of rows.
+struct LineBuilder<'a> {
+    title: &'a str,
+    title_width: usize,
+    title_style: Style,
+    header_style: Style,
+    sep_style: Style,
+    divider: bool,
+    right_align_labels: bool,
+    label_on_right: bool,
+    right_align_values: bool,
+    field_sep: &'a str,
+    use_fill: bool,
+}
+
+impl LineBuilder<'_> {
+    /// The value as styled pieces: padding for right alignment, the bar, then the text.
+    fn value_pieces(&self, f: &FieldRow, max_value_width: usize) -> Vec<(String, Style)> {
+        let mut pieces = Vec::new();
+        let pad = max_value_width.saturating_sub(f.value_width());
+        if pad > 0 {
+            pieces.push((" ".repeat(pad), Style::default()));
         }
+        if let Some(g) = &f.bar {
+            let (filled, empty) = g.cells();
+            pieces.push((
+                filled,
+                Style {
+                    color: Some(g.level.color()),
+                    ..Default::default()
+                },
+            ));
+            pieces.push((
+                empty,
+                Style {
+                    color: Some(Color::DarkGray),
+                    ..Default::default()
+                },
+            ));
+            if !f.value.is_empty() {
+                pieces.push((" ".into(), Style::default()));
+            }
+        }
+        pieces.push((f.value.clone(), f.value_style));
+        pieces
     }
 
-    // Calculate max widths for alignment
-    let max_label_width = field_data.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
-    let max_value_width = if right_align_values {
-        field_data.iter().map(|(_, v)| v.len()).max().unwrap_or(0)
-    } else {
-        0
-    };
-
-    // Total field width for fill separator
-    let fill_total = max_label_width + max_value_width + 6; // padding for fill line
+    fn lines(&self, rows: &[&Row]) -> Vec<Line> {
+        let field_data: Vec<&FieldRow> = rows
+            .iter()
+            .filter_map(|r| match r {
+                Row::Field(f) => Some(f),
+                _ => None,
+            })
+            .collect();
 
-    for (label, value) in &field_data {
-        let formatted_value = if right_align_values {
-            format!("{:>width$}", value, width = max_value_width)
+        // Calculate max widths for alignment
+        let max_label_width = field_data.iter().map(|f| text::width(&f.label)).max().unwrap_or(0);
+        let max_value_width = if self.right_align_values {
+            field_data.iter().map(|f| f.value_width()).max().unwrap_or(0)
         } else {
-            value.clone()
+            0
         };
 
-        if label_on_right {
-            // Value first, then label: "MacOS 15.5  arm64   OS"
-            let formatted_label = if right_align_labels {
-                format!("{:>width$}", label, width = max_label_width)
-            } else {
-                label.clone()
+        // Total field width for fill separator
+        let fill_total = max_label_width + max_value_width + 6; // padding for fill line
+
+        let mut lines: Vec<Line> = Vec::new();
+        for row in rows {
+            let f = match row {
+                Row::Title => {
+                    lines.push(Line::text(vec![(self.title.to_string(), self.title_style)], 0));
+                    if self.divider {
+                        lines.push(Line::Divider(self.title_width));
+                    }
+                    continue;
+                }
+                Row::Header(text) => {
+                    lines.push(Line::text(vec![(text.clone(), self.header_style)], 0));
+                    continue;
+                }
+                Row::Spacer => {
+                    lines.push(Line::text(vec![], 0));
+                    continue;
+                }
+                Row::Field(f) => f,
             };
-            let sep = if use_fill {
-                let used = formatted_value.len() + formatted_label.len() + 2;
+            let (label, label_style) = (&f.label, f.label_style);
+            let value = self.value_pieces(f, max_value_width);
+            let value_width: usize = value.iter().map(|(s, _)| text::width(s)).sum();
+
+            if self.label_on_right {
+                // Value first, then label: "MacOS 15.5  arm64   OS"
+                let formatted_label = if self.right_align_labels {
+                    text::pad_left(label, max_label_width)
+                } else {
+                    label.clone()
+                };
+                let sep = if self.use_fill {
+                    let used = value_width + text::width(&formatted_label) + 2;
+                    let fill_len = fill_total.saturating_sub(used);
+                    format!(" {} ", "─".repeat(fill_len))
+                } else {
+                    format!(" {}", self.field_sep)
+                };
+                let shrink = value.len() - 1;
+                let mut pieces = value;
+                pieces.push((sep, self.sep_style));
+                pieces.push((formatted_label, label_style));
+                lines.push(Line::text(pieces, shrink));
+            } else if self.use_fill {
+                // Label with fill line to value: "OS ──────── MacOS 15.5"
+                let formatted_label = if self.right_align_labels {
+                    text::pad_left(label, max_label_width)
+                } else {
+                    label.clone()
+                };
+                let used = text::width(&formatted_label) + value_width + 2;
                 let fill_len = fill_total.saturating_sub(used);
-                format!(" {} ", "─".repeat(fill_len))
-            } else {
-                format!(" {}", field_sep)
-            };
-            info_elements.push(element! {
-                Box(flex_direction: FlexDirection::Row) {
-                    Text(content: formatted_value)
-                    Text(content: sep, color: theme.separator)
-                    Text(content: formatted_label, color: theme.label, bold: true)
-                }
-            });
-        } else if use_fill {
-            // Label with fill line to value: "OS ──────── MacOS 15.5"
-            let formatted_label = if right_align_labels {
-                format!("{:>width$}", label, width = max_label_width)
+                let fill = format!(" {} ", "─".repeat(fill_len));
+                let mut pieces = vec![(formatted_label, label_style), (fill, self.sep_style)];
+                pieces.extend(value);
+                let shrink = pieces.len() - 1;
+                lines.push(Line::text(pieces, shrink));
             } else {
-                label.clone()
+                // Standard: "OS: MacOS 15.5"
+                let formatted_label = if self.right_align_labels {
+                    format!("{}{}", text::pad_left(label, max_label_width), self.field_sep)
+                } else {
+                    format!("{}{}", label, self.field_sep)
+                };
+                let mut pieces = vec![(formatted_label, label_style)];
+                pieces.extend(value);
+                let shrink = pieces.len() - 1;
+                lines.push(Line::text(pieces, shrink));
+            }
+        }
+        lines
+    }
+}
+
+/// Columns between info columns.
+const COLUMN_GAP: usize = 3;
+
+/// The info column's lines: a leading title across the top, then one or more
+/// side-by-side columns that each align on their own.
+struct Block {
+    head: Vec<Line>,
+    columns: Vec<Vec<Line>>,
+}
+
+impl Block {
+    fn new(builder: &LineBuilder, rows: &[Row], columns: usize) -> Self {
+        let rows: Vec<&Row> = rows.iter().collect();
+        if columns <= 1 {
+            return Block {
+                head: Vec::new(),
+                columns: vec![builder.lines(&rows)],
             };
-            let used = formatted_label.len() + formatted_value.len() + 2;
-            let fill_len = fill_total.saturating_sub(used);
-            let fill = format!(" {} ", "─".repeat(fill_len));
-            info_elements.push(element! {
-                Box(flex_direction: FlexDirection::Row) {
-                    Text(content: formatted_label, color: theme.label, bold: true)
-                    Text(content: fill, color: theme.separator)
-                    Text(content: formatted_value)
-                }
-            });
+        }
+
+        // A title at the top spans all columns; the rest flows into balanced columns
+        let split = rows.iter().take_while(|r| matches!(r, Row::Title)).count();
+        let (head, body) = rows.split_at(split);
+        let per_column = body.len().div_ceil(columns).max(1);
+        Block {
+            head: builder.lines(head),
+            columns: body.chunks(per_column).map(|c| builder.lines(c)).collect(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        let columns: usize = self
+            .columns
+            .iter()
+            .map(|c| c.iter().map(Line::width).max().unwrap_or(0))
+            .sum::<usize>()
+            + COLUMN_GAP * self.columns.len().saturating_sub(1);
+        self.head.iter().map(Line::width).max().unwrap_or(0).max(columns)
+    }
+
+    fn height(&self) -> usize {
+        self.head.len() + self.columns.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Cut every line to `max` columns. Only used with a single column.
+    fn truncate(&mut self, max: usize) {
+        for line in self.head.iter_mut().chain(self.columns.iter_mut().flatten()) {
+            line.truncate(max);
+        }
+    }
+
+    fn elements(self, cfg: &Config, theme: &Theme) -> Vec<Element> {
+        let mut elements: Vec<Element> = self.head.into_iter().map(|l| l.element(cfg, theme)).collect();
+        let mut columns: Vec<Element> = self
+            .columns
+            .into_iter()
+            .map(|c| {
+                Element::node::<Box>(
+                    BoxProps {
+                        flex_direction: FlexDirection::Column,
+                        ..Default::default()
+                    },
+                    c.into_iter().map(|l| l.element(cfg, theme)).collect(),
+                )
+            })
+            .collect();
+        if columns.len() == 1 {
+            elements.push(columns.remove(0));
         } else {
-            // Standard: "OS: MacOS 15.5"
-            let formatted_label = if right_align_labels {
-                format!("{:>width$}{}", label, field_sep, width = max_label_width)
-            } else {
-                format!("{}{}", label, field_sep)
-            };
-            info_elements.push(element! {
-                Box(flex_direction: FlexDirection::Row) {
-                    Text(content: formatted_label, color: theme.label, bold: true)
-                    Text(content: formatted_value)
+            elements.push(Element::node::<Box>(
+                BoxProps {
+                    flex_direction: FlexDirection::Row,
+                    gap: COLUMN_GAP as f32,
+                    ..Default::default()
+                },
+                columns,
+            ));
+        }
+        elements
+    }
+}
+
+/// Color swatches from the `palette_*` keys.
+struct Palette {
+    rows: Vec<[Color; 8]>,
+    swatch: String,
+    spacing: usize,
+    /// Under the logo rather than under the info
+    under_logo: bool,
+}
+
+impl Palette {
+    fn from_config(cfg: &Config) -> Self {
+        let glyph = cfg.palette_glyph.as_deref().unwrap_or("blocks");
+        let (glyph, width, spacing) = match glyph {
+            "blocks" => ("█", 3, 0),
+            "circles" => ("●", 1, 1),
+            g => (g, 1, 1),
+        };
+        let width = cfg.palette_width.map_or(width, usize::from).max(1);
+        let spacing = cfg.palette_spacing.map_or(spacing, usize::from);
+
+        let mut rows = match cfg.palette_colors.as_deref() {
+            Some("256") => vec![
+                [196, 208, 226, 46, 51, 21, 93, 201].map(Color::Indexed),
+                [232, 235, 238, 241, 244, 247, 250, 253].map(Color::Indexed),
+            ],
+            Some("truecolor") => vec![
+                std::array::from_fn(|i| hsv(i as f32 * 45.0, 1.0, 1.0)),
+                std::array::from_fn(|i| hsv(i as f32 * 45.0, 0.45, 1.0)),
+            ],
+            _ => vec![
+                [
+                    Color::Black,
+                    Color::Red,
+                    Color::Green,
+                    Color::Yellow,
+                    Color::Blue,
+                    Color::Magenta,
+                    Color::Cyan,
+                    Color::White,
+                ],
+                [
+                    Color::DarkGray,
+                    Color::LightRed,
+                    Color::LightGreen,
+                    Color::LightYellow,
+                    Color::LightBlue,
+                    Color::LightMagenta,
+                    Color::LightCyan,
+                    Color::White,
+                ],
+            ],
+        };
+        rows.truncate(cfg.palette_rows.unwrap_or(2).clamp(1, 2) as usize);
+
+        Palette {
+            rows,
+            swatch: glyph.repeat(width),
+            spacing,
+            under_logo: cfg.palette_position.as_deref() == Some("logo"),
+        }
+    }
+
+    fn width(&self) -> usize {
+        8 * text::width(&self.swatch) + 7 * self.spacing
+    }
+
+    /// Lines taken up, including the blank line above.
+    fn height(&self) -> usize {
+        self.rows.len() + 1
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        let mut elements = vec![element! { Text(content: "") }];
+        for row in &self.rows {
+            let swatches: Vec<Element> = row
+                .iter()
+                .map(|c| element! { Text(content: self.swatch.clone(), color: *c) })
+                .collect();
+            elements.push(Element::node::<Box>(
+                BoxProps {
+                    flex_direction: FlexDirection::Row,
+                    gap: self.spacing as f32,
+                    ..Default::default()
+                },
+                swatches,
+            ));
+        }
+        elements
+    }
+}
+
+/// Truecolor from hue (degrees), saturation and value (0-1).
+fn hsv(h: f32, s: f32, v: f32) -> Color {
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (h / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    let byte = |f: f32| ((f + m) * 255.0).round() as u8;
+    Color::Rgb(byte(r), byte(g), byte(b))
+}
+
+/// Columns between the logo and the info column when side by side.
+const GAP: usize = 3;
+
+/// Logo position and spacing from `logo_position`, `logo_padding` and `vertical_align`.
+struct LogoLayout {
+    /// Logo above or below the info rather than beside it
+    vertical: bool,
+    /// Logo after the info (right or bottom)
+    after: bool,
+    top: usize,
+    left: usize,
+    gap: usize,
+    align: AlignItems,
+}
+
+impl LogoLayout {
+    fn from_config(cfg: &Config) -> Self {
+        let position = cfg.logo_position.as_deref().unwrap_or("left");
+        let vertical = matches!(position, "top" | "bottom");
+        let pad = cfg.logo_padding.clone().unwrap_or_default();
+        LogoLayout {
+            vertical,
+            after: matches!(position, "right" | "bottom"),
+            top: pad.top.unwrap_or(0) as usize,
+            left: pad.left.unwrap_or(0) as usize,
+            gap: pad.gap.map(|g| g as usize).unwrap_or(if vertical { 1 } else { GAP }),
+            align: match cfg.vertical_align.as_deref() {
+                Some("top") => AlignItems::Start,
+                Some("bottom") => AlignItems::End,
+                _ => AlignItems::Center,
+            },
+        }
+    }
+
+    /// Columns the logo and info need together in this layout.
+    fn width(&self, logo_width: usize, info_width: usize) -> usize {
+        let logo_width = logo_width + self.left;
+        if self.vertical {
+            logo_width.max(info_width)
+        } else {
+            logo_width + self.gap + info_width
+        }
+    }
+}
+
+/// One line of the info column, before it becomes an element.
+enum Line {
+    /// Styled pieces side by side; `shrink` is the piece to truncate when too wide.
+    Text { pieces: Vec<(String, Style)>, shrink: usize },
+    /// The title divider, with its width
+    Divider(usize),
+}
+
+impl Line {
+    fn text(pieces: Vec<(String, Style)>, shrink: usize) -> Self {
+        Line::Text { pieces, shrink }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Line::Text { pieces, .. } => pieces.iter().map(|(s, _)| text::width(s)).sum(),
+            Line::Divider(w) => *w,
+        }
+    }
+
+    /// Fit within `max` columns by cutting the shrinkable piece, with an ellipsis.
+    fn truncate(&mut self, max: usize) {
+        let over = self.width().saturating_sub(max);
+        if over == 0 {
+            return;
+        }
+        match self {
+            Line::Text { pieces, shrink } => {
+                if let Some((s, _)) = pieces.get_mut(*shrink) {
+                    let keep = text::width(s).saturating_sub(over);
+                    *s = text::truncate(s.trim_end(), keep);
                 }
-            });
+            }
+            Line::Divider(w) => *w = max,
         }
     }
 
-    // Color palette
-    if cfg.show_palette_for_mode(mode) {
-        info_elements.push(element! { Text(content: "") });
-        info_elements.push(element! {
-            Box(flex_direction: FlexDirection::Row) {
-                Text(content: "███", color: Color::Black)
-                Text(content: "███", color: Color::Red)
-                Text(content: "███", color: Color::Green)
-                Text(content: "███", color: Color::Yellow)
-                Text(content: "███", color: Color::Blue)
-                Text(content: "███", color: Color::Magenta)
-                Text(content: "███", color: Color::Cyan)
-                Text(content: "███", color: Color::White)
+    fn element(self, cfg: &Config, theme: &Theme) -> Element {
+        match self {
+            Line::Text { mut pieces, .. } if pieces.len() == 1 => {
+                let (s, style) = pieces.remove(0);
+                style.text(s)
             }
-        });
-        info_elements.push(element! {
-            Box(flex_direction: FlexDirection::Row) {
-                Text(content: "███", color: Color::DarkGray)
-                Text(content: "███", color: Color::LightRed)
-                Text(content: "███", color: Color::LightGreen)
-                Text(content: "███", color: Color::LightYellow)
-                Text(content: "███", color: Color::LightBlue)
-                Text(content: "███", color: Color::LightMagenta)
-                Text(content: "███", color: Color::LightCyan)
-                Text(content: "███", color: Color::White)
+            Line::Text { pieces, .. } if pieces.is_empty() => element! { Text(content: "") },
+            Line::Text { pieces, .. } => Element::node::<Box>(
+                BoxProps {
+                    flex_direction: FlexDirection::Row,
+                    ..Default::default()
+                },
+                pieces.into_iter().map(|(s, style)| style.text(s)).collect(),
+            ),
+            Line::Divider(width) => divider(cfg, width, theme).unwrap_or_else(|| element! { Text(content: "") }),
+        }
+    }
+}
+
+/// Columns a frame adds: a border and a space of padding on each side.
+const FRAME_WIDTH: usize = 4;
+
+/// The `border` box around the info column or the whole output.
+struct Frame {
+    style: BorderStyle,
+    color: Color,
+    title: Option<String>,
+    around_all: bool,
+}
+
+impl Frame {
+    fn from_config(info: &SystemInfo, theme: &Theme, cfg: &Config) -> Option<Self> {
+        let style = match cfg.border.as_deref()? {
+            "rounded" => BorderStyle::Round,
+            "single" => BorderStyle::Single,
+            "double" => BorderStyle::Double,
+            "bold" => BorderStyle::Bold,
+            "ascii" => BorderStyle::Classic,
+            _ => return None,
+        };
+        let color = match cfg.colors.as_ref().and_then(|c| c.border.as_ref()) {
+            Some(c) => c.to_color(),
+            None => theme.separator,
+        };
+        let title = cfg
+            .border_title
+            .as_ref()
+            .map(|t| field::apply(info, "Title", &info.title(), t))
+            .filter(|t| !t.is_empty());
+        Some(Frame {
+            style,
+            color,
+            title,
+            around_all: cfg.border_around.as_deref() == Some("all"),
+        })
+    }
+
+    /// Box `content` of the given width. blaeck has no border titles, so with a
+    /// title the top edge is drawn as text above a box open at the top.
+    fn wrap(&self, content: Element, width: usize) -> Element {
+        let props = BoxProps {
+            flex_direction: FlexDirection::Column,
+            border_style: self.style,
+            border_color: Some(self.color),
+            padding_left: Some(1.0),
+            padding_right: Some(1.0),
+            ..Default::default()
+        };
+        let Some(title) = &self.title else {
+            return Element::node::<Box>(props, vec![content]);
+        };
+
+        let c = self.style.chars();
+        let inner = width + FRAME_WIDTH - 2;
+        let title = text::truncate(title, inner.saturating_sub(4));
+        let rest = inner.saturating_sub(text::width(&title) + 3);
+        let top = format!(
+            "{}{} {} {}{}",
+            c.top_left,
+            c.horizontal,
+            title,
+            c.horizontal.to_string().repeat(rest),
+            c.top_right
+        );
+        let body = Element::node::<Box>(
+            BoxProps {
+                width: Some((width + FRAME_WIDTH) as f32),
+                border_sides: Some(BorderSides {
+                    top: false,
+                    bottom: true,
+                    left: true,
+                    right: true,
+                }),
+                ..props
+            },
+            vec![content],
+        );
+        Element::node::<Box>(
+            BoxProps {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            vec![element! { Text(content: top, color: self.color) }, body],
+        )
+    }
+}
+
+/// Where the logo goes relative to the info column.
+#[derive(Clone, Copy, PartialEq)]
+enum Placement {
+    /// Where `logo_position` puts it
+    Configured,
+    /// Logo above the info
+    Stacked,
+    NoLogo,
+    /// No logo, and lines cut to this many columns
+    Truncated(usize),
+}
+
+impl Placement {
+    /// The first layout that fits the terminal, from the configured one down to truncated.
+    fn fit(layout: &LogoLayout, logo_width: usize, info_width: usize, term_width: usize) -> Self {
+        if logo_width == 0 || layout.width(logo_width, info_width) <= term_width {
+            if info_width <= term_width {
+                Placement::Configured
+            } else {
+                Placement::Truncated(term_width)
             }
-        });
+        } else if logo_width <= term_width && info_width <= term_width {
+            Placement::Stacked
+        } else if info_width <= term_width {
+            Placement::NoLogo
+        } else {
+            Placement::Truncated(term_width)
+        }
     }
+}
+
+/// Width of the terminal on stdout, or None when not a terminal (output is
+/// piped) or when `responsive = false`.
+fn terminal_width(cfg: &Config) -> Option<usize> {
+    if cfg.responsive == Some(false) {
+        return None;
+    }
+    terminal_columns()
+}
+
+/// Columns of the terminal on stdout, if it is one.
+fn terminal_columns() -> Option<usize> {
+    unsafe {
+        let mut ws: libc::winsize = std::mem::zeroed();
+        if libc::isatty(1) == 1 && libc::ioctl(1, libc::TIOCGWINSZ, &mut ws) == 0 && ws.ws_col > 0 {
+            return Some(ws.ws_col as usize);
+        }
+    }
+    None
+}
+
+/// Width to lay out in off screen (plain output that is piped, and exports),
+/// so long lines are not clipped.
+const PLAIN_WIDTH: u16 = 1000;
+
+/// Write the element to stdout, laid out at the terminal width, or at
+/// `PLAIN_WIDTH` when piped. Without color, it is laid out off screen and
+/// written as plain text with every escape removed.
+fn output(ui: Element, color: bool) -> io::Result<()> {
+    let width = terminal_columns().map_or(PLAIN_WIDTH, |w| w as u16);
+    if color {
+        let mut blaeck = Blaeck::with_size(io::stdout(), width, 24)?;
+        blaeck.render(ui)?;
+        return blaeck.unmount();
+    }
+
+    let mut buf = Vec::new();
+    let mut blaeck = Blaeck::with_size(&mut buf, width, 24)?;
+    blaeck.render(ui)?;
+    blaeck.unmount()?;
+    drop(blaeck);
+
+    let text = text::strip_escapes(&String::from_utf8_lossy(&buf));
+    let mut out = io::stdout().lock();
+    for line in text.lines() {
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// Fields as `Label: value` lines, for scripts and logs: no logo, title or escapes.
+pub fn render_plain(info: &SystemInfo, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for row in rows(info, theme, cfg, mode) {
+        if let Row::Field(f) = row {
+            writeln!(out, "{}: {}", f.label, f.value)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn render(info: &SystemInfo, logo: &str, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<()> {
+    draw(info, logo, theme, cfg, mode).map(|_| ())
+}
+
+/// What `draw` laid out, for the logo animation.
+struct Drawn {
+    placement: Placement,
+    /// Lines the logo and info column take up side by side
+    height: usize,
+}
+
+/// Render once and report where the logo ended up.
+fn draw(info: &SystemInfo, logo: &str, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<Drawn> {
+    let color = color::enabled(cfg.color_mode.as_deref());
+    let (ui, drawn) = layout(info, logo, theme, cfg, mode, terminal_width(cfg), color);
+    output(ui, color)?;
+    Ok(drawn)
+}
+
+/// The full render as ANSI text, laid out as configured rather than fitted to
+/// the terminal, with 24-bit colors. For `--export`.
+pub fn capture(info: &SystemInfo, logo: &str, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<String> {
+    // blaeck downgrades RGB to 256 colors, losing the moon's shading, unless
+    // COLORTERM claims truecolor. It has no per-instance setting: it reads the
+    // variable once per process and caches the answer. So claim truecolor only
+    // while that cache is filled, and put the variable back before rendering.
+    // Nothing asks earlier in an export, and no other thread reads the environment.
+    let saved = std::env::var_os("COLORTERM");
+    std::env::set_var("COLORTERM", "truecolor");
+    blaeck::supports_truecolor();
+    match saved {
+        Some(v) => std::env::set_var("COLORTERM", v),
+        None => std::env::remove_var("COLORTERM"),
+    }
+
+    let (ui, _) = layout(info, logo, theme, cfg, mode, None, true);
+    let mut buf = Vec::new();
+    let mut blaeck = Blaeck::with_size(&mut buf, PLAIN_WIDTH, 24)?;
+    blaeck.render(ui)?;
+    blaeck.unmount()?;
+    drop(blaeck);
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Build the element tree. `term` is the width to fit into, if any; without
+/// `color` the palette is left out.
+fn layout(
+    info: &SystemInfo,
+    logo: &str,
+    theme: &Theme,
+    cfg: &Config,
+    mode: Mode,
+    term: Option<usize>,
+    color: bool,
+) -> (Element, Drawn) {
+    let title = match &cfg.title_format {
+        Some(template) => field::apply(info, "Title", &info.title(), template),
+        None => info.title(),
+    };
+    let title_width = text::width(&title);
+
+    // Info fields — filtered and ordered by config
+    let layout = cfg.layout_for_mode(mode);
+    let title_style = Style {
+        color: Some(theme.title),
+        bold: true,
+        ..Default::default()
+    };
+    let field_sep = layout.field_separator.as_deref().unwrap_or(": ");
+    let builder = LineBuilder {
+        title: &title,
+        title_width,
+        title_style,
+        header_style: title_style.with(cfg.style.as_ref().and_then(|s| s.header.as_ref())),
+        sep_style: Style {
+            color: Some(theme.separator),
+            ..Default::default()
+        },
+        divider: cfg.divider.as_deref() != Some("none"),
+        right_align_labels: layout.label_align.as_deref() == Some("right"),
+        label_on_right: layout.label_position.as_deref() == Some("right"),
+        right_align_values: layout.value_align.as_deref() == Some("right"),
+        field_sep,
+        use_fill: field_sep == "fill",
+    };
+
+    let rows = rows(info, theme, cfg, mode);
+    let columns = layout.columns.unwrap_or(1).max(1) as usize;
+    let mut block = Block::new(&builder, &rows, columns);
+
+    // A palette means nothing without color
+    let palette = (color && cfg.show_palette_for_mode(mode)).then(|| Palette::from_config(cfg));
+    let info_palette = palette.as_ref().filter(|p| !p.under_logo);
+    let palette_width = info_palette.map_or(0, Palette::width);
 
     // Logo element — use per-cell colored moon for default mode, otherwise text
     let use_moon = mode == Mode::Default && logo.is_empty();
-    let logo_element = if use_moon {
+    let (logo_width, logo_height) = if use_moon {
+        let grid = logo::moon_grid();
+        (grid.iter().map(Vec::len).max().unwrap_or(0), grid.len())
+    } else {
+        (logo.lines().map(text::width).max().unwrap_or(0), logo.lines().count())
+    };
+    let logo_palette = palette.as_ref().filter(|p| p.under_logo);
+    let logo_width = logo_width.max(logo_palette.map_or(0, Palette::width));
+    let logo_height = logo_height + logo_palette.map_or(0, Palette::height);
+
+    let logo_layout = LogoLayout::from_config(cfg);
+    let frame = Frame::from_config(info, theme, cfg);
+    let term = term.map(|t| t.saturating_sub(frame.as_ref().map_or(0, |_| FRAME_WIDTH)));
+    let fit = |block: &Block| match term {
+        Some(term) => Placement::fit(&logo_layout, logo_width, block.width().max(palette_width), term),
+        None => Placement::Configured,
+    };
+    let mut placement = fit(&block);
+    // Columns are the first thing to give up when the layout does not fit
+    if placement != Placement::Configured && columns > 1 {
+        block = Block::new(&builder, &rows, 1);
+        placement = fit(&block);
+    }
+
+    // The palette moves under the info once the logo is gone
+    let mut info_palette = match placement {
+        Placement::NoLogo | Placement::Truncated(_) => palette.as_ref(),
+        _ => info_palette,
+    };
+    if let Placement::Truncated(term) = placement {
+        block.truncate(term);
+        info_palette = info_palette.filter(|p| p.width() <= term);
+    }
+    let info_width = block.width().max(info_palette.map_or(0, Palette::width));
+    let mut info_height = block.height();
+
+    let mut info_elements = block.elements(cfg, theme);
+
+    // Color palette
+    if let Some(p) = info_palette {
+        info_height += p.height();
+        info_elements.extend(p.elements());
+    }
+
+    let mut logo_element = if use_moon {
         moon_element()
     } else {
         element! { Text(content: logo, color: theme.logo) }
     };
-
-    let ui = Element::node::<Box>(
+    if let Some(p) = logo_palette {
+        let mut children = vec![logo_element];
+        children.extend(p.elements());
+        logo_element = Element::node::<Box>(
+            BoxProps {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            children,
+        );
+    }
+    let mut info_column = Element::node::<Box>(
         BoxProps {
-            flex_direction: FlexDirection::Row,
-            align_items: Some(AlignItems::Center),
+            flex_direction: FlexDirection::Column,
             ..Default::default()
         },
-        vec![
-            logo_element,
-            element! {
-                Text(content: "   ")
-            },
+        info_elements,
+    );
+    let frame_all = frame.as_ref().filter(|f| f.around_all);
+    if let Some(f) = frame.as_ref().filter(|f| !f.around_all) {
+        info_column = f.wrap(info_column, info_width);
+        info_height += 2;
+    }
+    let info_width = info_width + frame.as_ref().filter(|f| !f.around_all).map_or(0, |_| FRAME_WIDTH);
+
+    let ui = match placement {
+        Placement::Configured => {
+            let logo_box = Element::node::<Box>(
+                BoxProps {
+                    padding_top: Some(logo_layout.top as f32),
+                    padding_left: Some(logo_layout.left as f32),
+                    ..Default::default()
+                },
+                vec![logo_element],
+            );
+            let children = if logo_layout.after {
+                vec![info_column, logo_box]
+            } else {
+                vec![logo_box, info_column]
+            };
             Element::node::<Box>(
                 BoxProps {
-                    flex_direction: FlexDirection::Column,
+                    flex_direction: if logo_layout.vertical {
+                        FlexDirection::Column
+                    } else {
+                        FlexDirection::Row
+                    },
+                    align_items: Some(if logo_layout.vertical {
+                        AlignItems::Start
+                    } else {
+                        logo_layout.align
+                    }),
+                    gap: logo_layout.gap as f32,
                     ..Default::default()
                 },
-                info_elements,
-            ),
-        ],
-    );
-
-    let mut blaeck = Blaeck::new(io::stdout())?;
-    blaeck.render(ui)?;
-    blaeck.unmount()?;
+                children,
+            )
+        }
+        Placement::Stacked => Element::node::<Box>(
+            BoxProps {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            vec![logo_element, element! { Text(content: "") }, info_column],
+        ),
+        Placement::NoLogo | Placement::Truncated(_) => info_column,
+    };
+    let ui = match frame_all {
+        Some(f) => {
+            let width = match placement {
+                Placement::Configured => logo_layout.width(logo_width, info_width),
+                Placement::Stacked => logo_width.max(info_width),
+                Placement::NoLogo | Placement::Truncated(_) => info_width,
+            };
+            f.wrap(ui, width)
+        }
+        None => ui,
+    };
 
-    Ok(())
+    let drawn = Drawn {
+        placement,
+        height: info_height.max(logo_layout.top + logo_height),
+    };
+    (ui, drawn)
 }
 
 pub fn render_animated(info: &SystemInfo, logo: &str, theme: &Theme, cfg: &Config, mode: Mode) -> io::Result<()> {
     // Normal render first
-    render(info, logo, theme, cfg, mode)?;
+    let Drawn { placement, height } = draw(info, logo, theme, cfg, mode)?;
 
-    if logo.is_empty() {
+    // Animation repaints the logo from the left edge, top-aligned with the
+    // info; nothing to do if the logo is anywhere else
+    let layout = LogoLayout::from_config(cfg);
+    let at_left = !layout.vertical && !layout.after && matches!(layout.align, AlignItems::Start | AlignItems::Center);
+    let frame = Frame::from_config(info, theme, cfg);
+    if logo.is_empty() || placement != Placement::Configured || !at_left || frame.as_ref().is_some_and(|f| f.around_all) {
+        return Ok(());
+    }
+    // Color cycling needs color
+    if !color::enabled(cfg.color_mode.as_deref()) {
         return Ok(());
     }
 
-    // Calculate geometry
-    let logo_lines = logo.lines().count();
-    let info_lines = {
-        let active = cfg.active_fields_for_mode(mode);
-        let mut count = 2; // title + separator
-        count += active.len();
-        if cfg.show_palette_for_mode(mode) {
-            count += 3; // blank + 2 palette rows
-        }
-        count
-    };
-    let total_height = std::cmp::max(logo_lines, info_lines) as u16;
+    // Repaint the padding too, so rows and columns line up with the render
+    let indent = " ".repeat(layout.left);
+    let logo: String = std::iter::repeat_n("", layout.top)
+        .chain(logo.lines())
+        .map(|l| format!("{}{}\n", indent, l))
+        .collect();
+    let logo = logo.as_str();
+
+    let total_height = height as u16;
 
     let original_color = color::color_to_ansi(&theme.logo);
 
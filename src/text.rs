@@ -0,0 +1,78 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns a string takes up in the terminal. Each grapheme cluster counts
+/// once, so combining marks add nothing and an emoji ZWJ sequence is one glyph.
+pub fn width(s: &str) -> usize {
+    s.graphemes(true).map(|g| g.width().min(2)).sum()
+}
+
+/// Pad with spaces on the right to `width` columns.
+pub fn pad_right(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(self::width(s))))
+}
+
+/// Pad with spaces on the left to `width` columns.
+pub fn pad_left(s: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(self::width(s))), s)
+}
+
+/// Pad with spaces on both sides to `width` columns, extra space on the right.
+pub fn pad_center(s: &str, width: usize) -> String {
+    let pad = width.saturating_sub(self::width(s));
+    format!("{}{}{}", " ".repeat(pad / 2), s, " ".repeat(pad - pad / 2))
+}
+
+/// Cut to at most `width` columns, ending in `…` if anything was removed.
+pub fn truncate(s: &str, width: usize) -> String {
+    if self::width(s) <= width {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for g in s.graphemes(true) {
+        let w = g.width().min(2);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push_str(g);
+        used += w;
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Remove terminal escape sequences (colors, cursor movement, OSC) and
+/// carriage returns, leaving the text as it appears on screen.
+pub fn strip_escapes(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters, then a final byte in @..~
+                Some('[') => {
+                    for ch in chars.by_ref() {
+                        if ('@'..='~').contains(&ch) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: ends at BEL or ESC \
+                Some(']') => {
+                    while let Some(ch) = chars.next() {
+                        if ch == '\x07' || (ch == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Read an XDG path variable. Per the spec, empty or relative values are ignored.
+fn env_path(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
+
+fn home() -> Option<PathBuf> {
+    env_path("HOME")
+}
+
+/// `$XDG_CONFIG_HOME/blaeckfetch`, defaulting to `~/.config/blaeckfetch`.
+pub fn config_home() -> Option<PathBuf> {
+    env_path("XDG_CONFIG_HOME")
+        .or_else(|| home().map(|h| h.join(".config")))
+        .map(|p| p.join("blaeckfetch"))
+}
+
+/// System-wide config directories from `$XDG_CONFIG_DIRS` (default `/etc/xdg`),
+/// most important first.
+pub fn config_dirs() -> Vec<PathBuf> {
+    let dirs: Vec<PathBuf> = std::env::var("XDG_CONFIG_DIRS")
+        .unwrap_or_default()
+        .split(':')
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .collect();
+    let dirs = if dirs.is_empty() {
+        vec![PathBuf::from("/etc/xdg")]
+    } else {
+        dirs
+    };
+    dirs.into_iter().map(|p| p.join("blaeckfetch")).collect()
+}
+
+/// `$XDG_CACHE_HOME/blaeckfetch`, defaulting to `~/.cache/blaeckfetch`.
+/// Without a home directory, falls back to a private per-user runtime directory.
+pub fn cache_home() -> Option<PathBuf> {
+    env_path("XDG_CACHE_HOME")
+        .or_else(|| home().map(|h| h.join(".cache")))
+        .map(|p| p.join("blaeckfetch"))
+        .or_else(runtime_dir)
+}
+
+/// Per-user directory with mode 0700: `$XDG_RUNTIME_DIR/blaeckfetch`, or
+/// `/tmp/blaeckfetch-<uid>` when no runtime dir is set.
+/// Returns None if the directory exists but is not safely ours.
+pub fn runtime_dir() -> Option<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    let dir = match env_path("XDG_RUNTIME_DIR") {
+        Some(p) => p.join("blaeckfetch"),
+        None => std::env::temp_dir().join(format!("blaeckfetch-{}", unsafe { libc::getuid() })),
+    };
+
+    let _ = fs::DirBuilder::new().mode(0o700).create(&dir);
+
+    // symlink_metadata so a planted symlink in /tmp is rejected rather than followed
+    let meta = fs::symlink_metadata(&dir).ok()?;
+    let uid = unsafe { libc::getuid() };
+    if !meta.is_dir() || meta.uid() != uid {
+        return None;
+    }
+    if meta.permissions().mode() & 0o077 != 0 {
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).ok()?;
+    }
+    Some(dir)
+}